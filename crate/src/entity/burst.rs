@@ -0,0 +1,90 @@
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    entity::PaperPlane,
+    render::{Renderer, Transform},
+    types::{Clock, Hit, Rect},
+};
+
+/// The number of ticks a Burst is shown after it hits
+const BURST_TICKS: u64 = 12;
+
+/// A splash that damages every Plane around the point where a shot hit
+pub struct Burst {
+    sprite: String,
+    x: f64,
+    y: f64,
+    radius: f64,
+    /// The share of the damage lost at the edge of the radius
+    falloff: f64,
+    tick: u64,
+}
+
+impl Burst {
+    /// Constructs a new Burst drawn with the named sprite centered on (x, y) at the Clock's
+    /// tick
+    pub fn new(sprite: &str, x: f64, y: f64, radius: f64, falloff: f64, clock: &Clock) -> Self {
+        Self {
+            sprite: String::from(sprite),
+            x,
+            y,
+            radius,
+            falloff,
+            tick: clock.tick(),
+        }
+    }
+
+    /// Return the x-coordinate of the center of the Burst
+    pub fn x(&self) -> f64 {
+        self.x
+    }
+    /// Return the y-coordinate of the center of the Burst
+    pub fn y(&self) -> f64 {
+        self.y
+    }
+    /// Returns the radius of the Burst in pixels
+    pub fn radius(&self) -> f64 {
+        self.radius
+    }
+
+    /// Damage every living Plane the Burst reaches, dealing less the further the Plane is
+    /// from its center
+    ///
+    /// Camouflaged Planes are spared unless they are revealed or the Hit has detection.
+    pub fn hit(&self, planes: &mut [PaperPlane], hit: &Hit, clock: &Clock) {
+        let reached = planes
+            .iter_mut()
+            .filter(|plane| !plane.hp().is_dead())
+            .filter(|plane| plane.targetable() || hit.detection());
+        for plane in reached {
+            let distance = (plane.center_x() - self.x).hypot(plane.center_y() - self.y);
+            if distance > self.radius + plane.w() / 2.0 {
+                continue;
+            }
+            let scale = 1.0 - self.falloff * (distance / self.radius).min(1.0);
+            plane.take_hit(&hit.scaled(scale), clock);
+        }
+    }
+
+    /// Returns true once the Burst has been shown for `BURST_TICKS` ticks
+    pub fn expired(&self, clock: &Clock) -> bool {
+        clock.since(self.tick) >= BURST_TICKS
+    }
+
+    /// Draw the Burst with the referenced Renderer fading out as it expires
+    pub fn draw(&self, renderer: &mut dyn Renderer, clock: &Clock) -> Result<(), JsValue> {
+        renderer.set_alpha(1.0 - clock.since(self.tick) as f64 / BURST_TICKS as f64);
+        renderer.draw_sprite(
+            &self.sprite,
+            &Transform::new(self.x, self.y, 0.0),
+            &Rect::new(
+                (-self.radius).floor(),
+                (-self.radius).floor(),
+                (self.radius * 2.0).floor(),
+                (self.radius * 2.0).floor(),
+            ),
+        )?;
+        renderer.set_alpha(1.0);
+        Ok(())
+    }
+}
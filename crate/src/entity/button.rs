@@ -1,10 +1,9 @@
-use std::collections::HashMap;
-
 use wasm_bindgen::prelude::*;
 
-use web_sys::{CanvasRenderingContext2d, HtmlImageElement};
-
-use crate::types::{ButtonType, Rect};
+use crate::{
+    render::{Renderer, Transform},
+    types::{ButtonType, Rect, TargetMode},
+};
 
 #[wasm_bindgen]
 #[derive(Debug)]
@@ -26,95 +25,82 @@ impl Button {
         }
     }
 
-    /// Constructs a new Upgrade Button
-    pub fn new_upgrade(rect: Rect) -> Self {
+    /// Constructs a new Upgrade Button into the tier at the given index
+    pub fn new_upgrade(rect: Rect, tier: usize, label: &str) -> Self {
         Button::new(
             Rect::new(rect.x(), rect.y(), rect.w(), rect.h()),
-            ButtonType::Upgrade,
-            "Upgrade",
+            ButtonType::Upgrade(tier),
+            label,
         )
     }
 
     /// Constructs a new Delete Button
-    pub fn new_delete(rect: Rect) -> Self {
+    pub fn new_delete(rect: Rect, label: &str) -> Self {
         Button::new(
             Rect::new(rect.x(), rect.y(), rect.w(), rect.h()),
             ButtonType::Delete,
-            "Delete",
+            label,
         )
     }
 
+    /// Constructs a new Button that cycles a Tower's TargetMode
+    pub fn new_target(rect: Rect, mode: TargetMode) -> Self {
+        Button::new(rect, ButtonType::Target, mode.label())
+    }
+
     /// Draws the selection highlight for the Button
-    fn draw_selection(&self, ctx: &CanvasRenderingContext2d) {
-        ctx.begin_path();
-        ctx.set_stroke_style(&JsValue::from_str("#00ff00"));
-        ctx.rect(
-            self.x(),
-            self.y() - self.h() * 0.05,
-            self.w(),
-            self.h() + self.h() * 0.1,
-        );
-        ctx.stroke();
-        ctx.close_path();
+    fn draw_selection(&self, renderer: &mut dyn Renderer) -> Result<(), JsValue> {
+        renderer.stroke_rect(
+            &Rect::new(
+                self.x(),
+                self.y() - self.h() * 0.05,
+                self.w(),
+                self.h() + self.h() * 0.1,
+            ),
+            "#00ff00",
+        )
     }
 
     /// Draw a button with image content
-    fn draw_image_button(
-        &self,
-        ctx: &CanvasRenderingContext2d,
-        img: &HtmlImageElement,
-    ) -> Result<(), JsValue> {
-        ctx.begin_path();
-        ctx.set_fill_style(&JsValue::from_str("#222222"));
-        ctx.rect(
-            self.rect.x(),
-            self.rect.y() - self.h() * 0.05,
-            self.rect.w(),
-            self.rect.h() + self.h() * 0.1,
-        );
-        ctx.fill();
-        ctx.draw_image_with_html_image_element_and_dw_and_dh(
-            img,
-            self.rect.x(),
-            self.rect.y(),
-            self.rect.w(),
-            self.rect.h(),
+    fn draw_image_button(&self, renderer: &mut dyn Renderer) -> Result<(), JsValue> {
+        renderer.fill_rect(
+            &Rect::new(
+                self.rect.x(),
+                self.rect.y() - self.h() * 0.05,
+                self.rect.w(),
+                self.rect.h() + self.h() * 0.1,
+            ),
+            "#222222",
         )?;
-        ctx.close_path();
-        Ok(())
+        renderer.draw_sprite(&self.content, &Transform::identity(), &self.rect)
     }
 
     /// Draw a button with text content
-    fn draw_text_button(&self, ctx: &CanvasRenderingContext2d) -> Result<(), JsValue> {
-        ctx.begin_path();
-        ctx.set_fill_style(&JsValue::from_str("#222222"));
-        ctx.rect(self.rect.x(), self.rect.y(), self.rect.w(), self.rect.h());
-        ctx.fill();
-        ctx.set_fill_style(&JsValue::from_str("#00ff00"));
-        ctx.set_font(&format!("{}px monospace", self.rect.w() * 0.2));
-        ctx.fill_text(
+    fn draw_text_button(&self, renderer: &mut dyn Renderer) -> Result<(), JsValue> {
+        // shrink long labels so they fit the width of the Button
+        let len = self.content.chars().count().max(1) as f64;
+        let font_size = (self.rect.w() * 0.2).min(self.rect.w() * 1.4 / len);
+
+        renderer.fill_rect(&self.rect, "#222222")?;
+        renderer.fill_text(
             &self.content,
             self.rect.x() + self.rect.w() * 0.07,
             self.rect.y() + self.rect.h() * 0.6,
-        )?;
-        ctx.close_path();
-        Ok(())
+            &format!("{}px monospace", font_size),
+            "#00ff00",
+        )
     }
 
     /// Draws the button
-    pub fn draw(
-        &self,
-        ctx: &CanvasRenderingContext2d,
-        sprites: &HashMap<String, HtmlImageElement>,
-    ) -> Result<(), JsValue> {
-        if let Some(img) = sprites.get(&self.content) {
-            self.draw_image_button(ctx, &img)
+    pub fn draw(&self, renderer: &mut dyn Renderer) -> Result<(), JsValue> {
+        if renderer.has_sprite(&self.content) {
+            self.draw_image_button(renderer)
         } else {
-            self.draw_text_button(ctx)
+            self.draw_text_button(renderer)
         }?;
 
         if self.selected {
-            self.draw_selection(ctx)
+            self.draw_selection(renderer)?;
         }
         Ok(())
     }
@@ -1,7 +1,12 @@
+mod burst;
 mod button;
 mod plane;
+mod projectile;
 mod tower;
 
+pub use burst::Burst;
 pub use button::Button;
 pub use plane::PaperPlane;
+pub use projectile::Projectile;
+pub use tower::Shot;
 pub use tower::Tower;
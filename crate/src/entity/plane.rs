@@ -1,127 +1,108 @@
-use std::{collections::HashMap, f64::consts::PI};
+use std::rc::Rc;
 
 use wasm_bindgen::prelude::*;
 
-use web_sys::{CanvasRenderingContext2d, HtmlImageElement};
-
-use crate::types::{Direction, HitPoints, PlanePath, Rect};
+use crate::{
+    render::{Renderer, Transform},
+    types::{
+        Clock, DamageType, EffectKind, Hit, HitPoints, PathProgress, PlaneChild, PlaneKind,
+        PlanePath, PlaneTrait, Rect, StatusEffect, WaveModifiers,
+    },
+};
 
 /// An entity spawned by the game to get to the end a map and reduce the player's HP
 #[wasm_bindgen]
 pub struct PaperPlane {
+    /// A number no other Plane of the same Simulation shares
+    id: u64,
+    kind: Rc<PlaneKind>,
+    path: Rc<PlanePath>,
     rect: Rect,
     rotation: f64,
+    /// Distance travelled in pixels per tick
     speed: f64,
-    dx: f64,
-    dy: f64,
-    img: String,
+    progress: PathProgress,
     hp: HitPoints,
+    /// The multipliers of the Wave the Plane was spawned by, which its children inherit
+    modifiers: WaveModifiers,
+    effects: Vec<StatusEffect>,
+    /// Whether a Tower with detection has the Plane in range, which lets every Tower see it
+    revealed: bool,
+    /// The number of boss phases the Plane has begun
+    phase: usize,
+    /// The multiplier the current boss phase applies to the Plane's speed
+    phase_speed: f64,
+    /// The tick the Plane's shield wears off at
+    shield: u64,
     damage: u32,
     bounty: u32,
 }
 
 impl PaperPlane {
-    /// Constructs a new basic Plane
-    pub fn new_basic(rect: Rect) -> Self {
+    /// Constructs a new Plane with the given id of the referenced kind and size at the start
+    /// of the referenced path
+    pub fn spawn(id: u64, kind: &Rc<PlaneKind>, path: &Rc<PlanePath>, size: f64) -> Self {
+        let (x, y) = path.point_at(0.0);
         Self {
-            rect,
-            rotation: 0.0,
-            speed: 1.3,
-            dx: 1.3,
-            dy: 0.0,
-            img: String::from("Plane"),
-            hp: HitPoints::new(10),
-            damage: 1,
-            bounty: 1,
+            id,
+            kind: Rc::clone(kind),
+            path: Rc::clone(path),
+            rect: Rect::new(x - size / 2.0, y - size / 2.0, size, size),
+            rotation: path.angle_of(0),
+            speed: kind.speed(),
+            progress: PathProgress::new(),
+            hp: HitPoints::new(kind.hp()),
+            modifiers: WaveModifiers::default(),
+            effects: Vec::new(),
+            revealed: false,
+            phase: 0,
+            phase_speed: 1.0,
+            shield: 0,
+            damage: kind.damage(),
+            bounty: kind.bounty(),
         }
     }
 
-    /// Constructs a new Bullet
-    pub fn new_bullet(rect: Rect) -> Self {
-        Self {
-            rect,
-            rotation: 0.0,
-            speed: 3.0,
-            dx: 3.0,
-            dy: 0.0,
-            img: String::from("Bullet"),
-            hp: HitPoints::new(15),
-            damage: 2,
-            bounty: 2,
-        }
-    }
-    /// Constructs a new Bullet Redux
-    pub fn new_bullet_redux(rect: Rect) -> Self {
-        Self {
-            rect,
-            rotation: 0.0,
-            speed: 4.0,
-            dx: 4.0,
-            dy: 0.0,
-            img: String::from("BulletRedux"),
-            hp: HitPoints::new(30),
-            damage: 2,
-            bounty: 5,
-        }
+    /// Constructs a new Plane with the given id of the referenced kind and size the given
+    /// distance along the referenced path, clamped to its ends
+    pub fn spawn_at(
+        id: u64,
+        kind: &Rc<PlaneKind>,
+        path: &Rc<PlanePath>,
+        size: f64,
+        distance: f64,
+    ) -> Self {
+        let mut plane = Self::spawn(id, kind, path, size);
+        plane.progress.advance(distance.clamp(0.0, path.length()));
+        plane.follow_path();
+        plane
     }
 
-    /// Constructs a new Glider
-    pub fn new_glider(rect: Rect) -> Self {
-        Self {
-            rect,
-            rotation: 0.0,
-            speed: 1.7,
-            dx: 1.7,
-            dy: 0.0,
-            img: String::from("Glider"),
-            hp: HitPoints::new(50),
-            damage: 2,
-            bounty: 3,
-        }
-    }
-    /// Constructs a new Glider Redux
-    pub fn new_glider_redux(rect: Rect) -> Self {
-        Self {
-            rect,
-            rotation: 0.0,
-            speed: 1.7,
-            dx: 1.7,
-            dy: 0.0,
-            img: String::from("GliderRedux"),
-            hp: HitPoints::new(60),
-            damage: 2,
-            bounty: 5,
-        }
+    /// Scale the HP, speed, and bounty of the Plane by the modifiers of its Wave
+    pub fn apply_modifiers(&mut self, modifiers: &WaveModifiers) {
+        self.modifiers = *modifiers;
+        let hp = (self.hp.max_hp() as f64 * modifiers.hp()).round() as u32;
+        self.hp = HitPoints::new(hp.max(1));
+        self.speed *= modifiers.speed();
+        self.bounty = (self.bounty as f64 * modifiers.bounty()).round() as u32;
     }
 
-    /// Constructs a new Water Bomb
-    pub fn new_waterbomb(rect: Rect) -> Self {
-        Self {
-            rect,
-            rotation: 0.0,
-            speed: 1.0,
-            dx: 1.0,
-            dy: 0.0,
-            img: String::from("WaterBomb"),
-            hp: HitPoints::new(100),
-            damage: 3,
-            bounty: 10,
-        }
+    /// Returns the multipliers of the Wave the Plane was spawned by
+    pub fn modifiers(&self) -> &WaveModifiers {
+        &self.modifiers
     }
 
-    /// Constructs a new Blimp
-    pub fn new_blimp(rect: Rect) -> Self {
-        Self {
-            rect,
-            rotation: 0.0,
-            speed: 1.0,
-            dx: 1.0,
-            dy: 0.0,
-            img: String::from("Blimp"),
-            hp: HitPoints::new(200),
-            damage: 5,
-            bounty: 10,
-        }
+    /// Returns the id of the Plane
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+    /// Returns the kind of the Plane
+    pub fn kind(&self) -> &PlaneKind {
+        &self.kind
+    }
+    /// Returns the path the Plane follows
+    pub fn path(&self) -> &Rc<PlanePath> {
+        &self.path
     }
 
     /// Return the x-coordinate of the Plane
@@ -158,6 +139,62 @@ impl PaperPlane {
         self.bounty
     }
 
+    /// Returns true if the Plane is camouflaged from Towers without detection
+    pub fn camo(&self) -> bool {
+        self.kind.has_trait(PlaneTrait::Camo)
+    }
+    /// Returns true if a Tower with detection has the Plane in range, revealing it to every
+    /// Tower
+    pub fn revealed(&self) -> bool {
+        self.revealed
+    }
+    /// Set whether a Tower with detection has the Plane in range
+    pub fn set_revealed(&mut self, revealed: bool) {
+        self.revealed = revealed;
+    }
+    /// Returns true if Towers without detection can target the Plane
+    pub fn targetable(&self) -> bool {
+        !self.camo() || self.revealed
+    }
+
+    /// Returns the number of boss phases the Plane has begun
+    pub fn phase(&self) -> usize {
+        self.phase
+    }
+    /// Returns true while the Plane's shield keeps it from taking damage
+    pub fn shielded(&self, clock: &Clock) -> bool {
+        clock.tick() < self.shield
+    }
+
+    /// Begin every boss phase whose share of HP the Plane has fallen to, changing its speed
+    /// and raising its shield
+    ///
+    /// Returns the escorts to spawn behind the Plane.
+    pub fn begin_phases(&mut self, clock: &Clock) -> Vec<PlaneChild> {
+        let mut escorts = Vec::new();
+        if self.hp.is_dead() {
+            return escorts;
+        }
+        let kind = Rc::clone(&self.kind);
+        while let Some(phase) = kind.phases().get(self.phase) {
+            if self.hp.percent() > phase.hp() {
+                break;
+            }
+            self.phase_speed = phase.speed();
+            if phase.shield() > 0 {
+                self.shield = clock.tick() + phase.shield();
+            }
+            escorts.extend_from_slice(phase.escorts());
+            self.phase += 1;
+        }
+        escorts
+    }
+
+    /// Returns how far the Plane has made it along its path
+    pub fn progress(&self) -> &PathProgress {
+        &self.progress
+    }
+
     /// Returns a reference to the Plane's HP
     pub fn hp(&self) -> &HitPoints {
         &self.hp
@@ -167,71 +204,184 @@ impl PaperPlane {
         &mut self.hp
     }
 
-    /// Advance the location of the Plane by one increment
-    pub fn fly(&mut self, path: &PlanePath) {
-        for turn in path.turns().iter() {
-            if turn.touching(&self.rect) {
-                match turn.direction() {
-                    Direction::Up => {
-                        self.dx = 0.0;
-                        self.dy = -self.speed;
-                        self.rotation = PI * 1.5;
-                    }
-                    Direction::Down => {
-                        self.dx = 0.0;
-                        self.dy = self.speed;
-                        self.rotation = PI * 0.5;
-                    }
-                    Direction::Left => {
-                        self.dx = -self.speed;
-                        self.rotation = PI;
-                        self.dy = 0.0;
-                    }
-                    Direction::Right => {
-                        self.dx = self.speed;
-                        self.rotation = 0.0;
-                        self.dy = 0.0;
-                    }
-                }
+    /// Returns the effects the Plane carries
+    pub fn effects(&self) -> &[StatusEffect] {
+        &self.effects
+    }
+    /// Returns true if the Plane carries the named effect
+    pub fn has_effect(&self, name: &str) -> bool {
+        self.effects
+            .iter()
+            .any(|effect| effect.kind().name() == name)
+    }
+
+    /// Returns true if the Plane has a trait that makes it immune to the referenced effect
+    pub fn immune_to(&self, kind: &EffectKind) -> bool {
+        kind.immune().iter().any(|&t| self.kind.has_trait(t))
+    }
+    /// Returns the share of its speed the Plane keeps under the effects slowing it
+    pub fn speed_factor(&self) -> f64 {
+        self.effects
+            .iter()
+            .map(|effect| 1.0 - effect.kind().slow())
+            .product()
+    }
+
+    /// Add a stack of the referenced effect dealing damage of the given type to the Plane
+    /// unless it is immune, spreading within the given radius in pixels if any
+    pub fn afflict(
+        &mut self,
+        kind: &EffectKind,
+        damage_type: DamageType,
+        spread: Option<f64>,
+        clock: &Clock,
+    ) {
+        if self.immune_to(kind) {
+            return;
+        }
+        match self
+            .effects
+            .iter_mut()
+            .find(|effect| effect.kind().name() == kind.name())
+        {
+            Some(effect) => effect.restack(spread, clock),
+            None => self
+                .effects
+                .push(StatusEffect::new(kind, damage_type, spread, clock)),
+        }
+    }
+
+    /// Returns the damage the Plane takes from `damage` of the given type once its resistance
+    /// applies and, if `armored`, its armor lowered by shredding is taken off
+    ///
+    /// Armor shredded below 0 adds to the damage taken.
+    pub fn resolve_damage(&self, damage: u32, damage_type: DamageType, armored: bool) -> u32 {
+        let resisted = (damage as f64 * (1.0 - self.kind.resistance(damage_type))).round();
+        let armor = if armored {
+            let shred: u32 = self.effects.iter().map(StatusEffect::shred).sum();
+            self.kind.armor() as f64 - shred as f64
+        } else {
+            0.0
+        };
+        (resisted - armor).max(0.0) as u32
+    }
+
+    /// Take the damage of the referenced Hit and its effects unless the Plane is shielded
+    pub fn take_hit(&mut self, hit: &Hit, clock: &Clock) {
+        if self.shielded(clock) {
+            return;
+        }
+        let damage = self.resolve_damage(hit.damage(), hit.damage_type(), true);
+        self.hp.take_damage(damage);
+        for kind in hit.effects() {
+            let spread = kind.spread().map(|r| r * hit.tower_size());
+            self.afflict(kind, hit.damage_type(), spread, clock);
+        }
+    }
+
+    /// Take the damage of every effect that is due, which armor does not stop, and drop the
+    /// effects that wore off, while the Plane's shield holds the damage off
+    ///
+    /// Returns the effects that spread at the Clock's tick.
+    pub fn update_effects(&mut self, clock: &Clock) -> Vec<StatusEffect> {
+        let shielded = self.shielded(clock);
+        let mut spreads = Vec::new();
+        for i in 0..self.effects.len() {
+            let damage = self.effects[i].tick(clock);
+            if damage == 0 || shielded {
+                continue;
+            }
+            let damage = self.resolve_damage(damage, self.effects[i].damage_type(), false);
+            self.hp.take_damage(damage);
+            if self.effects[i].spread().is_some() {
+                spreads.push(self.effects[i].clone());
             }
         }
+        self.effects.retain(|effect| !effect.expired(clock));
+        spreads
+    }
+
+    /// Advance the Plane along its path by one tick at its speed for the boss phase it is in,
+    /// slowed by its effects
+    pub fn fly(&mut self) {
+        self.progress
+            .advance(self.speed * self.phase_speed * self.speed_factor());
+        self.follow_path();
+    }
+
+    /// Move and turn the Plane to where its progress puts it on its path
+    fn follow_path(&mut self) {
+        let distance = self.progress.distance();
+        let segment = self.path.segment_at(distance);
+        self.progress.enter_segment(segment);
+
+        let (x, y) = self.path.point_at(distance);
         self.rect
-            .set_pos(self.rect.x() + self.dx, self.rect.y() + self.dy);
+            .set_pos(x - self.rect.w() / 2.0, y - self.rect.h() / 2.0);
+        self.rotation = self.path.angle_of(segment);
+    }
+
+    /// Returns the distance left before the Plane reaches the end of its path
+    pub fn remaining(&self) -> f64 {
+        self.path.length() - self.progress.distance()
+    }
+
+    /// Returns true once the Plane has reached the end of its path
+    pub fn finished(&self) -> bool {
+        self.progress.distance() >= self.path.length()
     }
 
     /// Draw the HP indicator of the Plane
-    fn draw_hp_bar(&self, ctx: &CanvasRenderingContext2d) -> Result<(), JsValue> {
-        ctx.begin_path();
-        ctx.set_fill_style(&JsValue::from_str("#00ff00"));
-        ctx.fill_rect(
-            (-self.rect.w() * 0.5).floor(),
-            (-self.rect.h() * 0.5).floor(),
-            (self.rect.w() * self.hp().percent()).floor(),
-            (self.rect.h() * 0.1).floor(),
-        );
-        ctx.close_path();
-
-        Ok(())
-    }
-
-    /// Draw the Plane on the referenced Context
-    pub fn draw(
-        &self,
-        ctx: &CanvasRenderingContext2d,
-        sprites: &HashMap<String, HtmlImageElement>,
-    ) -> Result<(), JsValue> {
-        ctx.translate(self.rect.center_x(), self.rect.center_y())?;
-        ctx.rotate(self.rotation)?;
-        ctx.draw_image_with_html_image_element_and_dw_and_dh(
-            sprites.get(&self.img).unwrap(),
-            (-self.rect.w() * 0.5).floor(),
-            (-self.rect.h() * 0.5).floor(),
-            self.rect.w().floor(),
-            self.rect.h().floor(),
-        )?;
-        self.draw_hp_bar(ctx)?;
-        ctx.set_transform(1.0, 0.0, 0.0, 1.0, 0.0, 0.0)?;
+    fn draw_hp_bar(&self, renderer: &mut dyn Renderer) -> Result<(), JsValue> {
+        renderer.fill_rect(
+            &Rect::new(
+                self.rect.x().floor(),
+                self.rect.y().floor(),
+                (self.rect.w() * self.hp().percent()).floor(),
+                (self.rect.h() * 0.1).floor(),
+            ),
+            "#00ff00",
+        )
+    }
 
-        Ok(())
+    /// Draw the Plane with the referenced Renderer, faded while it hides under camouflage and
+    /// ringed while shielded
+    ///
+    /// Bosses have their HP drawn in the top bar instead.
+    pub fn draw(&self, renderer: &mut dyn Renderer, clock: &Clock) -> Result<(), JsValue> {
+        if !self.targetable() {
+            renderer.set_alpha(0.4);
+        }
+        renderer.draw_sprite(
+            self.kind.sprite(),
+            &Transform::new(self.rect.center_x(), self.rect.center_y(), self.rotation),
+            &Rect::new(
+                (-self.rect.w() * 0.5).floor(),
+                (-self.rect.h() * 0.5).floor(),
+                self.rect.w().floor(),
+                self.rect.h().floor(),
+            ),
+        )?;
+        if !self.targetable() {
+            renderer.set_alpha(1.0);
+        }
+        if let Some(effect) = self.effects.last() {
+            renderer.set_alpha(0.4);
+            renderer.fill_rect(&self.rect, effect.kind().tint())?;
+            renderer.set_alpha(1.0);
+        }
+        if self.shielded(clock) {
+            renderer.stroke_ellipse(
+                self.rect.center_x(),
+                self.rect.center_y(),
+                self.rect.w() * 0.75,
+                self.rect.h() * 0.75,
+                "#00ffff",
+            )?;
+        }
+        if self.kind.boss() {
+            return Ok(());
+        }
+        self.draw_hp_bar(renderer)
     }
 }
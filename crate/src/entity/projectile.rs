@@ -0,0 +1,154 @@
+use std::f64::consts::PI;
+
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    entity::{Burst, PaperPlane},
+    render::{Renderer, Transform},
+    types::{point_to_segment, Clock, Hit, ProjectileKind, Rect, Splash},
+};
+
+/// A shot fired by a Tower that travels towards a Plane and damages the first Plane it reaches
+pub struct Projectile {
+    sprite: String,
+    x: f64,
+    y: f64,
+    size: f64,
+    angle: f64,
+    /// Distance travelled in pixels per tick
+    speed: f64,
+    homing: bool,
+    radius: f64,
+    /// The radius in pixels and falloff of the Burst made on impact, if any
+    splash: Option<(f64, f64)>,
+    hit: Hit,
+    /// The id of the Plane the Projectile was fired at
+    target: u64,
+    /// The distance the Projectile can still travel before it falls
+    reach: f64,
+    spent: bool,
+}
+
+impl Projectile {
+    /// Constructs a new Projectile of the referenced kind fired from the center of the
+    /// referenced Rect at the referenced Plane, travelling at most `reach` pixels and
+    /// splashing on impact if a Splash is given
+    pub fn fire(
+        kind: &ProjectileKind,
+        splash: Option<&Splash>,
+        from: &Rect,
+        target: &PaperPlane,
+        hit: Hit,
+        reach: f64,
+    ) -> Self {
+        let (x, y) = (from.center_x(), from.center_y());
+        Self {
+            sprite: String::from(kind.sprite()),
+            x,
+            y,
+            size: from.w() * 0.4,
+            angle: (target.center_y() - y).atan2(target.center_x() - x),
+            speed: kind.speed(),
+            homing: kind.homing(),
+            radius: from.w() * kind.radius(),
+            splash: splash.map(|splash| (from.w() * splash.radius(), splash.falloff())),
+            hit,
+            target: target.id(),
+            reach,
+            spent: false,
+        }
+    }
+
+    /// Return the x-coordinate of the center of the Projectile
+    pub fn x(&self) -> f64 {
+        self.x
+    }
+    /// Return the y-coordinate of the center of the Projectile
+    pub fn y(&self) -> f64 {
+        self.y
+    }
+    /// Returns the id of the Plane the Projectile was fired at
+    pub fn target(&self) -> u64 {
+        self.target
+    }
+    /// Returns true once the Projectile has hit a Plane or run out of reach
+    pub fn spent(&self) -> bool {
+        self.spent
+    }
+
+    /// Advance the Projectile by one tick and hit the first living Plane it passes within its
+    /// radius of, passing through camouflaged Planes its Tower cannot see
+    ///
+    /// Returns the Burst made if the Projectile splashes on impact.
+    pub fn fly(&mut self, planes: &mut [PaperPlane], clock: &Clock) -> Option<Burst> {
+        if self.spent {
+            return None;
+        }
+        if self.homing {
+            if let Some(plane) = planes.iter().find(|plane| plane.id() == self.target) {
+                self.angle = (plane.center_y() - self.y).atan2(plane.center_x() - self.x);
+            }
+        }
+
+        let step = self.speed.min(self.reach);
+        let start = (self.x, self.y);
+        let end = (
+            self.x + step * self.angle.cos(),
+            self.y + step * self.angle.sin(),
+        );
+        self.x = end.0;
+        self.y = end.1;
+        self.reach -= step;
+
+        // check the whole distance covered so fast Projectiles cannot pass through Planes
+        let from_start =
+            |plane: &PaperPlane| (plane.center_x() - start.0).hypot(plane.center_y() - start.1);
+        let hit = planes
+            .iter()
+            .enumerate()
+            .filter(|(_, plane)| !plane.hp().is_dead())
+            .filter(|(_, plane)| plane.targetable() || self.hit.detection())
+            .filter(|(_, plane)| {
+                let center = (plane.center_x(), plane.center_y());
+                point_to_segment(center, start, end) <= self.radius + plane.w() / 2.0
+            })
+            .min_by(|(_, a), (_, b)| from_start(a).total_cmp(&from_start(b)))
+            .map(|(i, _)| i);
+
+        match hit {
+            Some(i) => {
+                self.spent = true;
+                match self.splash {
+                    Some((radius, falloff)) => {
+                        let (x, y) = (planes[i].center_x(), planes[i].center_y());
+                        let burst = Burst::new(&self.sprite, x, y, radius, falloff, clock);
+                        burst.hit(planes, &self.hit, clock);
+                        Some(burst)
+                    }
+                    None => {
+                        planes[i].take_hit(&self.hit, clock);
+                        None
+                    }
+                }
+            }
+            None => {
+                self.spent = self.reach <= 0.0;
+                None
+            }
+        }
+    }
+
+    /// Draw the Projectile with the referenced Renderer
+    pub fn draw(&self, renderer: &mut dyn Renderer) -> Result<(), JsValue> {
+        renderer.draw_sprite(
+            &self.sprite,
+            &Transform::new(self.x, self.y, self.angle + PI * 0.5),
+            &Rect::new(
+                (-self.size * 0.5).floor(),
+                (-self.size * 0.5).floor(),
+                self.size.floor(),
+                self.size.floor(),
+            ),
+        )
+    }
+}
@@ -1,123 +1,195 @@
-use std::{collections::HashMap, f64::consts::PI};
+use std::{f64::consts::PI, rc::Rc};
 
 use wasm_bindgen::prelude::*;
 
-use js_sys::Date;
-
-use web_sys::{CanvasRenderingContext2d, HtmlImageElement};
-
 use crate::{
-    entity::{Button, PaperPlane},
-    types::{AcidTower, Mouse, Rect, SodaMaker, TowerStatus, TowerType, WaterGun},
+    entity::{Burst, Button, PaperPlane, Projectile},
+    render::{Renderer, Transform},
+    types::{ButtonType, Clock, Hit, Mouse, Rect, TargetMode, TowerFamily, TowerStatus, TowerTier},
 };
 
+/// The number of ticks the blast sprite is shown after the Tower fires
+const BLAST_TICKS: u64 = 6;
+/// The number of ticks after placement during which a Tower can be removed for a full refund
+const UNDO_TICKS: u64 = 180;
+/// The distance a Projectile travels before it falls as a multiple of the Tower's range
+const PROJECTILE_REACH: f64 = 1.5;
+
+/// What a Tower leaves behind when it fires
+pub enum Shot {
+    /// A Projectile travelling towards the Plane fired at
+    Projectile(Projectile),
+    /// The splash of a shot that hit instantly
+    Burst(Burst),
+}
+
 /// An entity the user spends cash to create in order to destroy Planes
 #[wasm_bindgen]
 pub struct Tower {
-    variant: TowerType,
+    family: Rc<TowerFamily>,
+    tier: usize,
 
     rect: Rect,
     rotation: f64,
 
-    base_img: String,
-    blast_img: String,
-    top_img: String,
-
-    upgrade_cost: i32,
-
-    upgrade_button: Button,
+    upgrade_buttons: Vec<Button>,
     delete_button: Button,
+    target_button: Button,
 
+    target_mode: TargetMode,
+    /// The cash spent building and upgrading the Tower
+    invested: i32,
+    sell_rate: f64,
     dmg: u32,
     dmg_interval: f64,
     range: f64,
-    last_dmg_time: f64,
+    last_dmg_tick: Option<u64>,
 
     status: TowerStatus,
     mouse_over: bool,
 }
 
 impl Tower {
-    fn new_upgrade_button(rect: &Rect) -> Button {
-        Button::new_upgrade(Rect::new(
-            rect.x().floor(),
-            (rect.y() - rect.h() * 0.6).floor(),
-            rect.w().floor(),
-            (rect.h() * 0.5).floor(),
-        ))
-    }
-    fn new_delete_button(rect: &Rect) -> Button {
-        Button::new_delete(Rect::new(
-            rect.x().floor(),
-            (rect.y() + rect.h() * 1.3).floor(),
-            rect.w().floor(),
-            (rect.h() * 0.5).floor(),
-        ))
-    }
-
-    /// Construct a new Water Gun
-    pub fn new_water_gun(rect: Rect) -> Self {
-        Self {
-            variant: TowerType::WaterGun(WaterGun::Basic),
-            upgrade_button: Tower::new_upgrade_button(&rect),
-            delete_button: Tower::new_delete_button(&rect),
-            range: (rect.h() * 2.5).floor(),
-            rect,
-            rotation: 0.0,
-            base_img: String::from("WaterGunBase"),
-            blast_img: String::from("WaterGunBlast"),
-            top_img: String::from("WaterGunTop"),
-            upgrade_cost: 10,
-            dmg: 5,
-            dmg_interval: 800.0,
-            last_dmg_time: 0.0,
-            status: TowerStatus::Normal,
-            mouse_over: false,
-        }
+    /// Lay out one Upgrade Button per branch of the current tier side by side above the Tower
+    fn new_upgrade_buttons(&self) -> Vec<Button> {
+        let upgrades = self.tier().upgrades();
+        let gap = self.rect.w() * 0.1;
+        let w = self.rect.w() * 1.5;
+        let total = w * upgrades.len() as f64 + gap * (upgrades.len() as f64 - 1.0);
+        let left = self.rect.center_x() - total / 2.0;
+
+        upgrades
+            .iter()
+            .enumerate()
+            .map(|(i, &tier)| {
+                Button::new_upgrade(
+                    Rect::new(
+                        (left + (w + gap) * i as f64).floor(),
+                        (self.rect.y() - self.rect.h() * 0.6).floor(),
+                        w.floor(),
+                        (self.rect.h() * 0.5).floor(),
+                    ),
+                    tier,
+                    self.family.tiers()[tier].name(),
+                )
+            })
+            .collect()
+    }
+    fn new_delete_button(&self) -> Button {
+        let label = match self.status {
+            TowerStatus::Placed { .. } => format!("Undo ${}", self.invested),
+            TowerStatus::ConfirmSell => format!("Confirm ${}", self.sell_price()),
+            _ => format!("Sell ${}", self.sell_price()),
+        };
+        Button::new_delete(
+            Rect::new(
+                self.rect.x().floor(),
+                (self.rect.y() + self.rect.h() * 1.3).floor(),
+                self.rect.w().floor(),
+                (self.rect.h() * 0.5).floor(),
+            ),
+            &label,
+        )
     }
 
-    /// Construct a new Acid Tower
-    pub fn new_acid_tower(rect: Rect) -> Self {
-        Self {
-            variant: TowerType::AcidTower(AcidTower::Basic),
-            upgrade_button: Tower::new_upgrade_button(&rect),
-            delete_button: Tower::new_delete_button(&rect),
-            range: rect.h() * 1.5,
-            rect,
-            rotation: 0.0,
-            base_img: String::from("WaterGunBase"),
-            blast_img: String::from("AcidTowerBlast"),
-            top_img: String::from("AcidTowerTop"),
-            upgrade_cost: 10,
-            dmg: 1,
-            dmg_interval: 100.0,
-            last_dmg_time: 0.0,
-            status: TowerStatus::Normal,
-            mouse_over: false,
-        }
+    fn new_target_button(rect: &Rect, mode: TargetMode) -> Button {
+        Button::new_target(
+            Rect::new(
+                rect.x().floor(),
+                (rect.y() + rect.h() * 1.9).floor(),
+                rect.w().floor(),
+                (rect.h() * 0.5).floor(),
+            ),
+            mode,
+        )
     }
 
-    /// Construct a new Soda Maker
-    pub fn new_soda_maker(rect: Rect) -> Self {
-        Self {
-            variant: TowerType::SodaMaker(SodaMaker::Basic),
-            upgrade_button: Tower::new_upgrade_button(&rect),
-            delete_button: Tower::new_delete_button(&rect),
-            range: rect.h() * 3.0,
+    /// Construct a new Tower of the first tier of the referenced family that sells for
+    /// `sell_rate` of the cash invested in it
+    pub fn build(family: &Rc<TowerFamily>, rect: Rect, sell_rate: f64) -> Self {
+        let mut tower = Self {
+            family: Rc::clone(family),
+            tier: 0,
+            upgrade_buttons: Vec::new(),
+            delete_button: Button::new_delete(rect.clone(), ""),
+            target_button: Tower::new_target_button(&rect, TargetMode::default()),
+            target_mode: TargetMode::default(),
+            invested: family.cost(),
+            sell_rate,
             rect,
             rotation: 0.0,
-            base_img: String::from("WaterGunBase"),
-            blast_img: String::from("SodaMakerBlast"),
-            top_img: String::from("SodaMakerTop"),
-            upgrade_cost: 10,
-            dmg: 20,
-            dmg_interval: 1000.0,
-            last_dmg_time: 0.0,
+            dmg: 0,
+            dmg_interval: 0.0,
+            range: 0.0,
+            last_dmg_tick: None,
             status: TowerStatus::Normal,
             mouse_over: false,
-        }
+        };
+        tower.apply_tier();
+        tower
     }
 
+    /// Take on the stats and upgrade branches of the current tier
+    fn apply_tier(&mut self) {
+        let tier = &self.family.tiers()[self.tier];
+        self.range = self.rect.h() * tier.range();
+        self.dmg = tier.damage();
+        self.dmg_interval = tier.interval();
+        self.upgrade_buttons = self.new_upgrade_buttons();
+        self.delete_button = self.new_delete_button();
+    }
+
+    /// Returns the family of the Tower
+    pub fn family(&self) -> &TowerFamily {
+        &self.family
+    }
+    /// Returns the current tier of the Tower
+    pub fn tier(&self) -> &TowerTier {
+        &self.family.tiers()[self.tier]
+    }
+    /// Returns the tiers the Tower can upgrade into next
+    pub fn upgrades(&self) -> impl Iterator<Item = &TowerTier> + '_ {
+        self.tier()
+            .upgrades()
+            .iter()
+            .map(move |&tier| &self.family.tiers()[tier])
+    }
+    /// Returns the Buttons that upgrade the Tower into each of its next tiers
+    pub fn upgrade_buttons(&self) -> &[Button] {
+        &self.upgrade_buttons
+    }
+    /// Returns the cash spent building and upgrading the Tower
+    pub fn invested(&self) -> i32 {
+        self.invested
+    }
+    /// Returns the cash selling the Tower refunds
+    pub fn sell_price(&self) -> i32 {
+        (self.invested as f64 * self.sell_rate).floor() as i32
+    }
+    /// Set the share of the cash invested that selling the Tower refunds
+    pub fn set_sell_rate(&mut self, sell_rate: f64) {
+        self.sell_rate = sell_rate;
+        self.delete_button = self.new_delete_button();
+    }
+    /// Returns the range of the Tower
+    pub fn range(&self) -> f64 {
+        self.range
+    }
+
+    /// Returns the rule the Tower uses to choose its target
+    pub fn target_mode(&self) -> TargetMode {
+        self.target_mode
+    }
+    /// Set the rule the Tower uses to choose its target
+    pub fn set_target_mode(&mut self, mode: TargetMode) {
+        self.target_mode = mode;
+        self.target_button = Tower::new_target_button(&self.rect, mode);
+    }
+
+    /// Returns the Rect the Tower covers
+    pub fn rect(&self) -> &Rect {
+        &self.rect
+    }
     /// Return the x-coordinate of the center of the tower
     pub fn center_x(&self) -> f64 {
         self.rect.center_x()
@@ -131,208 +203,336 @@ impl Tower {
     pub fn status(&self) -> TowerStatus {
         self.status
     }
+    /// Change the Tower's TowerStatus and the Delete Button to match
+    fn set_status(&mut self, status: TowerStatus) {
+        self.status = status;
+        self.delete_button = self.new_delete_button();
+    }
+
+    /// Select a Tower that was just placed and open its undo window
+    pub fn place(&mut self, clock: &Clock) {
+        self.set_status(TowerStatus::Placed {
+            until: clock.tick() + UNDO_TICKS,
+            selected: true,
+        });
+    }
 
-    /// Takes a reference to a Plane and applies damage if conditions are met
-    pub fn damage(&mut self, plane: &mut PaperPlane) {
+    /// Returns true if at least `dmg_interval` ticks have passed since the Tower last fired
+    fn reloaded(&self, clock: &Clock) -> bool {
+        match self.last_dmg_tick {
+            Some(tick) => clock.since(tick) as f64 >= self.dmg_interval,
+            None => true,
+        }
+    }
+
+    /// Returns true if the Tower fired within the last `BLAST_TICKS` ticks
+    fn firing(&self, clock: &Clock) -> bool {
+        match self.last_dmg_tick {
+            Some(tick) => clock.since(tick) < BLAST_TICKS,
+            None => false,
+        }
+    }
+
+    /// Returns the distance from the center of the Tower to the center of the Plane
+    fn distance(&self, plane: &PaperPlane) -> f64 {
         let dx = self.rect.center_x() - plane.center_x();
         let dy = self.rect.center_y() - plane.center_y();
-        let dist = (dx.powi(2) + dy.powi(2)).sqrt();
+        (dx.powi(2) + dy.powi(2)).sqrt()
+    }
 
-        if dist < self.range && Date::now() - self.last_dmg_time > self.dmg_interval {
-            self.last_dmg_time = Date::now();
+    /// Returns true if the Plane is within the Tower's range
+    pub fn in_range(&self, plane: &PaperPlane) -> bool {
+        self.distance(plane) < self.range
+    }
+    /// Returns true if the Tower can see camouflaged Planes
+    pub fn detects(&self) -> bool {
+        self.tier().detection()
+    }
 
-            if plane.center_y() > self.rect.center_y() {
-                self.rotation = PI - ((dx / dist).acos() + PI * 1.5);
-            } else {
-                self.rotation = (dx / dist).acos() + PI * 1.5;
+    /// Returns the index of the Plane in range the Tower would fire at under its TargetMode
+    ///
+    /// Camouflaged Planes are skipped unless the Tower has detection or they are revealed.
+    pub fn target(&self, planes: &[PaperPlane]) -> Option<usize> {
+        let in_range = planes
+            .iter()
+            .enumerate()
+            .filter(|(_, plane)| self.in_range(plane))
+            .filter(|(_, plane)| plane.targetable() || self.detects());
+
+        match self.target_mode {
+            TargetMode::First => {
+                in_range.min_by(|(_, a), (_, b)| a.remaining().total_cmp(&b.remaining()))
+            }
+            TargetMode::Last => {
+                in_range.min_by(|(_, a), (_, b)| b.remaining().total_cmp(&a.remaining()))
+            }
+            TargetMode::Strongest => {
+                in_range.min_by_key(|(_, plane)| std::cmp::Reverse(plane.hp().curr_hp()))
+            }
+            TargetMode::Weakest => in_range.min_by_key(|(_, plane)| plane.hp().curr_hp()),
+            TargetMode::Closest => {
+                in_range.min_by(|(_, a), (_, b)| self.distance(a).total_cmp(&self.distance(b)))
             }
+        }
+        .map(|(i, _)| i)
+    }
+
+    /// Choose a target among the Planes and fire at it if the Tower has reloaded
+    ///
+    /// Returns the Shot fired if the current tier's shots travel or splash.
+    pub fn attack(&mut self, planes: &mut [PaperPlane], clock: &Clock) -> Option<Shot> {
+        if !self.reloaded(clock) {
+            return None;
+        }
+        let i = self.target(planes)?;
+        self.fire(planes, i, clock)
+    }
+
+    /// Turn towards the Plane with the given index and either damage it and any Planes its
+    /// splash reaches or fire a Projectile at it
+    fn fire(&mut self, planes: &mut [PaperPlane], i: usize, clock: &Clock) -> Option<Shot> {
+        let plane = &planes[i];
+        let dx = self.rect.center_x() - plane.center_x();
+        let dist = self.distance(plane);
 
-            plane.hp_mut().take_damage(self.dmg);
+        self.last_dmg_tick = Some(clock.tick());
+
+        if plane.center_y() > self.rect.center_y() {
+            self.rotation = PI - ((dx / dist).acos() + PI * 1.5);
+        } else {
+            self.rotation = (dx / dist).acos() + PI * 1.5;
+        }
+
+        let tier = &self.family.tiers()[self.tier];
+        let hit = Hit::new(
+            self.dmg,
+            self.family.damage_type(),
+            tier.effects(),
+            self.rect.w(),
+            self.detects(),
+        );
+        if let Some(kind) = tier.projectile() {
+            return Some(Shot::Projectile(Projectile::fire(
+                kind,
+                tier.splash(),
+                &self.rect,
+                plane,
+                hit,
+                self.range * PROJECTILE_REACH,
+            )));
+        }
+        match tier.splash() {
+            Some(splash) => {
+                let burst = Burst::new(
+                    tier.blast(),
+                    plane.center_x(),
+                    plane.center_y(),
+                    self.rect.w() * splash.radius(),
+                    splash.falloff(),
+                    clock,
+                );
+                burst.hit(planes, &hit, clock);
+                Some(Shot::Burst(burst))
+            }
+            None => {
+                planes[i].take_hit(&hit, clock);
+                None
+            }
         }
     }
 
+    /// Draws a range ring of the given radius around the center of the referenced Rect
+    fn draw_range_ring(
+        renderer: &mut dyn Renderer,
+        rect: &Rect,
+        range: f64,
+        color: &str,
+    ) -> Result<(), JsValue> {
+        renderer.stroke_ellipse(rect.center_x(), rect.center_y(), range, range, color)
+    }
+
     /// Draws the circular range of the Tower
-    fn draw_range(&self, ctx: &CanvasRenderingContext2d) -> Result<(), JsValue> {
-        ctx.begin_path();
-        ctx.set_stroke_style(&JsValue::from_str("#ff0000"));
-        ctx.ellipse(
-            self.rect.center_x(),
-            self.rect.center_y(),
-            self.range,
-            self.range,
-            PI / 4.0,
-            0.0,
-            2.0 * PI,
-        )?;
-        ctx.stroke();
-        ctx.close_path();
-        Ok(())
+    fn draw_range(&self, renderer: &mut dyn Renderer) -> Result<(), JsValue> {
+        Tower::draw_range_ring(renderer, &self.rect, self.range, "#ff0000")
     }
 
     /// Indicates selection of the tower when drawn
-    fn draw_selection(&self, ctx: &CanvasRenderingContext2d) -> Result<(), JsValue> {
-        ctx.begin_path();
-        ctx.set_stroke_style(&JsValue::from_str("#00ff00"));
-        ctx.ellipse(
+    fn draw_selection(&self, renderer: &mut dyn Renderer) -> Result<(), JsValue> {
+        renderer.stroke_ellipse(
             self.rect.center_x(),
             self.rect.center_y(),
             self.rect.w() * 0.8,
             self.rect.h() * 0.8,
-            PI * 0.25,
-            0.0,
-            PI * 2.0,
-        )?;
-        ctx.stroke();
-        ctx.close_path();
-        Ok(())
+            "#00ff00",
+        )
     }
 
-    /// Draws the Tower on the referenced Context
-    pub fn draw(
-        &self,
-        ctx: &CanvasRenderingContext2d,
-        sprites: &HashMap<String, HtmlImageElement>,
+    /// Draws the named base sprite beneath a Tower covering the referenced Rect
+    fn draw_base(renderer: &mut dyn Renderer, base: &str, rect: &Rect) -> Result<(), JsValue> {
+        let base_size = rect.w() * 1.25;
+        renderer.draw_sprite(
+            base,
+            &Transform::identity(),
+            &Rect::new(
+                rect.center_x() - base_size * 0.5,
+                rect.center_y() - base_size / 2.5,
+                base_size,
+                base_size,
+            ),
+        )
+    }
+
+    /// Draws the named top sprite of a Tower covering the referenced Rect
+    fn draw_top(
+        renderer: &mut dyn Renderer,
+        top: &str,
+        rect: &Rect,
+        transform: &Transform,
     ) -> Result<(), JsValue> {
-        // draw tower base
-        let base_size = self.rect.w() * 1.25;
-        ctx.draw_image_with_html_image_element_and_dw_and_dh(
-            sprites.get(&self.base_img).unwrap(),
-            self.rect.center_x() - base_size * 0.5,
-            self.rect.center_y() - base_size / 2.5,
-            base_size,
-            base_size,
+        renderer.draw_sprite(
+            top,
+            transform,
+            &Rect::new(
+                (-rect.w() * 0.5).floor(),
+                (-rect.h() * 0.5).floor(),
+                rect.w().floor(),
+                rect.h().floor(),
+            ),
+        )
+    }
+
+    /// Draws a translucent preview of the first tier of the referenced family at the
+    /// referenced Rect, with its range ring in the given color
+    pub fn draw_ghost(
+        renderer: &mut dyn Renderer,
+        family: &TowerFamily,
+        rect: &Rect,
+        color: &str,
+    ) -> Result<(), JsValue> {
+        let tier = &family.tiers()[0];
+        renderer.set_alpha(0.5);
+        Tower::draw_base(renderer, family.base(), rect)?;
+        Tower::draw_top(
+            renderer,
+            tier.top(),
+            rect,
+            &Transform::new(rect.center_x(), rect.center_y(), 0.0),
         )?;
+        renderer.set_alpha(1.0);
+
+        Tower::draw_range_ring(renderer, rect, rect.h() * tier.range(), color)
+    }
+
+    /// Draws the Tower with the referenced Renderer as it appears at the Clock's tick
+    pub fn draw(&self, renderer: &mut dyn Renderer, clock: &Clock) -> Result<(), JsValue> {
+        Tower::draw_base(renderer, self.family.base(), &self.rect)?;
 
         // draw top sprite with potential blast sprite
-        ctx.translate(self.rect.center_x(), self.rect.center_y())?;
-        ctx.rotate(self.rotation)?;
-        if Date::now() - self.last_dmg_time < 100.0 {
-            ctx.draw_image_with_html_image_element_and_dw_and_dh(
-                sprites.get(&self.blast_img).unwrap(),
-                -self.rect.w() * 0.5,
-                -self.rect.h() * 1.4,
-                self.rect.w().floor(),
-                self.rect.h().floor(),
+        let transform = Transform::new(self.rect.center_x(), self.rect.center_y(), self.rotation);
+        if self.firing(clock) {
+            renderer.draw_sprite(
+                self.tier().blast(),
+                &transform,
+                &Rect::new(
+                    -self.rect.w() * 0.5,
+                    -self.rect.h() * 1.4,
+                    self.rect.w().floor(),
+                    self.rect.h().floor(),
+                ),
             )?;
         }
-        ctx.draw_image_with_html_image_element_and_dw_and_dh(
-            sprites.get(&self.top_img).unwrap(),
-            (-self.rect.w() * 0.5).floor(),
-            (-self.rect.h() * 0.5).floor(),
-            self.rect.w().floor(),
-            self.rect.h().floor(),
-        )?;
-        ctx.set_transform(1.0, 0.0, 0.0, 1.0, 0.0, 0.0)?;
+        Tower::draw_top(renderer, self.tier().top(), &self.rect, &transform)?;
 
         if self.mouse_over {
-            self.draw_range(ctx)?;
+            self.draw_range(renderer)?;
 
-            self.draw_selection(ctx)?;
+            self.draw_selection(renderer)?;
         }
 
-        if let TowerStatus::Selected = self.status {
-            self.draw_selection(ctx)?;
+        if self.status.selected() {
+            self.draw_selection(renderer)?;
 
-            self.upgrade_button.draw(ctx, sprites)?;
-            self.delete_button.draw(ctx, sprites)?;
+            for button in self.upgrade_buttons.iter() {
+                button.draw(renderer)?;
+            }
+            self.delete_button.draw(renderer)?;
+            self.target_button.draw(renderer)?;
         }
 
         Ok(())
     }
 
     /// Handle mouse interaction with the Tower
-    pub fn events(&mut self, mouse: &Mouse, cash: &mut i32) {
+    pub fn events(&mut self, mouse: &Mouse, cash: &mut i32, clock: &Clock) {
         self.mouse_over = mouse.inside(&self.rect);
 
-        if mouse.up() {
-            if let TowerStatus::Selected = self.status {
-                if mouse.inside(self.upgrade_button.rect()) {
-                    match self.variant {
-                        TowerType::WaterGun(WaterGun::Basic) => self.upgrade_water2(cash),
-                        TowerType::WaterGun(WaterGun::SuperSoaker) => self.upgrade_water3(cash),
-                        TowerType::AcidTower(AcidTower::Basic) => self.upgrade_acid2(cash),
-                        TowerType::SodaMaker(SodaMaker::Basic) => self.upgrade_soda2(cash),
-                        TowerType::SodaMaker(SodaMaker::SparklingWater) => self.upgrade_soda3(cash),
-                        _ => (),
-                    }
-                } else if mouse.inside(self.delete_button.rect()) {
-                    self.status = TowerStatus::Deleted;
-                    *cash += self.upgrade_cost;
-                    return;
-                }
-            }
-            if self.mouse_over {
-                if let TowerStatus::Selected = self.status {
-                    self.status = TowerStatus::Normal;
+        if let TowerStatus::Placed { until, selected } = self.status {
+            if clock.tick() >= until {
+                self.set_status(if selected {
+                    TowerStatus::Selected
                 } else {
-                    self.status = TowerStatus::Selected;
-                }
-            } else {
-                self.status = TowerStatus::Normal;
+                    TowerStatus::Normal
+                });
+            } else if clock.tick() + UNDO_TICKS == until {
+                // the click that placed the Tower does not deselect it
+                return;
             }
         }
-    }
 
-    /// Upgrade the tower to a Super Soaker
-    fn upgrade_water2(&mut self, cash: &mut i32) {
-        if *cash >= self.upgrade_cost {
-            self.top_img = String::from("SuperSoakerTop");
-            self.blast_img = String::from("SuperSoakerBlast");
-            self.variant = TowerType::WaterGun(WaterGun::SuperSoaker);
-            self.range *= 1.2;
-            self.dmg += 5;
-            self.dmg_interval *= 0.5;
-            *cash -= self.upgrade_cost;
-            self.upgrade_cost += 10;
-        }
-    }
-    /// Upgrade the tower to an Extreme Soaker
-    fn upgrade_water3(&mut self, cash: &mut i32) {
-        if *cash >= self.upgrade_cost {
-            self.top_img = String::from("ExtremeSoakerTop");
-            self.blast_img = String::from("ExtremeSoakerBlast");
-            self.variant = TowerType::WaterGun(WaterGun::ExtremeSoaker);
-            self.range *= 1.2;
-            self.dmg += 5;
-            self.dmg_interval *= 0.7;
-            *cash -= self.upgrade_cost;
+        if !mouse.up() {
+            return;
         }
-    }
-    /// Upgrade the tower to a Radioactive Tower
-    fn upgrade_acid2(&mut self, cash: &mut i32) {
-        if *cash >= self.upgrade_cost {
-            self.top_img = String::from("RadioactiveTowerTop");
-            self.blast_img = String::from("RadioactiveTowerBlast");
-            self.variant = TowerType::AcidTower(AcidTower::Radioactive);
-            self.range *= 1.1;
-            self.dmg *= 2;
-            self.dmg_interval *= 0.3;
-            *cash -= self.upgrade_cost;
-            self.upgrade_cost += 10;
+        if self.status.selected() {
+            let clicked = self
+                .upgrade_buttons
+                .iter()
+                .find(|button| mouse.inside(button.rect()))
+                .map(Button::button_type);
+            if let Some(ButtonType::Upgrade(tier)) = clicked {
+                self.upgrade(tier, cash);
+            } else if mouse.inside(self.target_button.rect()) {
+                self.set_target_mode(self.target_mode.next());
+                return;
+            } else if mouse.inside(self.delete_button.rect()) {
+                match self.status {
+                    TowerStatus::Placed { .. } => {
+                        *cash += self.invested;
+                        self.set_status(TowerStatus::Deleted);
+                    }
+                    TowerStatus::ConfirmSell => {
+                        *cash += self.sell_price();
+                        self.set_status(TowerStatus::Deleted);
+                    }
+                    _ => self.set_status(TowerStatus::ConfirmSell),
+                }
+                return;
+            }
         }
+
+        // any other click cancels a pending sell
+        let status = match (self.status, self.mouse_over) {
+            (TowerStatus::Placed { until, selected }, over) => TowerStatus::Placed {
+                until,
+                selected: over && !selected,
+            },
+            (TowerStatus::Normal, true) => TowerStatus::Selected,
+            _ => TowerStatus::Normal,
+        };
+        self.set_status(status);
     }
-    /// Upgrade the tower to a Sparkling Water Tower
-    fn upgrade_soda2(&mut self, cash: &mut i32) {
-        if *cash >= self.upgrade_cost {
-            self.top_img = String::from("SparklingWaterTop");
-            self.blast_img = String::from("SparklingWaterBlast");
-            self.variant = TowerType::SodaMaker(SodaMaker::SparklingWater);
-            self.range *= 1.2;
-            self.dmg += 10;
-            *cash -= self.upgrade_cost;
-            self.upgrade_cost += 10;
+
+    /// Upgrade the Tower into the tier at the given index of its family if it is a branch
+    /// of the current tier and the player can afford it
+    pub fn upgrade(&mut self, tier: usize, cash: &mut i32) {
+        if !self.tier().upgrades().contains(&tier) {
+            return;
         }
-    }
-    /// Upgrade the tower to a Root Beer Blaster
-    fn upgrade_soda3(&mut self, cash: &mut i32) {
-        if *cash >= self.upgrade_cost {
-            self.top_img = String::from("RootBeerTop");
-            self.blast_img = String::from("RootBeerBlast");
-            self.variant = TowerType::SodaMaker(SodaMaker::RootBeer);
-            self.range *= 1.2;
-            self.dmg += 20;
-            *cash -= self.upgrade_cost;
-            self.upgrade_cost += 10;
+        let cost = self.family.tiers()[tier].cost();
+        if *cash >= cost {
+            *cash -= cost;
+            self.invested += cost;
+            self.tier = tier;
+            self.apply_tier();
         }
     }
 }
@@ -1,9 +1,13 @@
 /// Various entities found within the game
-mod entity;
+pub mod entity;
 /// The Game struct
 mod game;
+/// Drawing surfaces the game can be rendered to
+pub mod render;
+/// The browser-independent rules and state of the game
+pub mod simulation;
 /// Custom types used within entities in the game
-mod types;
+pub mod types;
 /// Useful miscellaneous functions
 mod utils;
 
@@ -0,0 +1,113 @@
+use std::{collections::HashMap, f64::consts::PI};
+
+use wasm_bindgen::prelude::*;
+
+use web_sys::{CanvasRenderingContext2d, HtmlImageElement};
+
+use super::{Renderer, Transform};
+use crate::types::Rect;
+
+/// A Renderer that draws onto a 2D canvas context
+pub struct CanvasRenderer {
+    ctx: CanvasRenderingContext2d,
+    sprites: HashMap<String, HtmlImageElement>,
+}
+
+impl CanvasRenderer {
+    /// Construct a new CanvasRenderer loading each named sprite from `static/`
+    pub fn new(ctx: CanvasRenderingContext2d, sprite_names: &[&str]) -> Self {
+        let mut sprites = HashMap::new();
+        for sprite in sprite_names.iter() {
+            let img = HtmlImageElement::new().unwrap();
+            img.set_src(&format!("static/{}.png", sprite));
+            sprites.insert(String::from(*sprite), img);
+        }
+        Self { ctx, sprites }
+    }
+
+    /// Returns a reference to the underlying context
+    pub fn ctx(&self) -> &CanvasRenderingContext2d {
+        &self.ctx
+    }
+}
+
+impl Renderer for CanvasRenderer {
+    fn has_sprite(&self, name: &str) -> bool {
+        self.sprites.contains_key(name)
+    }
+
+    fn set_alpha(&mut self, alpha: f64) {
+        self.ctx.set_global_alpha(alpha);
+    }
+
+    fn draw_sprite(
+        &mut self,
+        name: &str,
+        transform: &Transform,
+        rect: &Rect,
+    ) -> Result<(), JsValue> {
+        let sprite = self
+            .sprites
+            .get(name)
+            .ok_or_else(|| JsValue::from_str(&format!("unknown sprite \"{}\"", name)))?;
+        self.ctx.translate(transform.x(), transform.y())?;
+        self.ctx.rotate(transform.rotation())?;
+        self.ctx.draw_image_with_html_image_element_and_dw_and_dh(
+            sprite,
+            rect.x(),
+            rect.y(),
+            rect.w(),
+            rect.h(),
+        )?;
+        self.ctx.set_transform(1.0, 0.0, 0.0, 1.0, 0.0, 0.0)
+    }
+
+    fn fill_rect(&mut self, rect: &Rect, color: &str) -> Result<(), JsValue> {
+        self.ctx.begin_path();
+        self.ctx.set_fill_style(&JsValue::from_str(color));
+        self.ctx.fill_rect(rect.x(), rect.y(), rect.w(), rect.h());
+        self.ctx.close_path();
+        Ok(())
+    }
+
+    fn stroke_rect(&mut self, rect: &Rect, color: &str) -> Result<(), JsValue> {
+        self.ctx.begin_path();
+        self.ctx.set_stroke_style(&JsValue::from_str(color));
+        self.ctx.stroke_rect(rect.x(), rect.y(), rect.w(), rect.h());
+        self.ctx.close_path();
+        Ok(())
+    }
+
+    fn stroke_ellipse(
+        &mut self,
+        x: f64,
+        y: f64,
+        radius_x: f64,
+        radius_y: f64,
+        color: &str,
+    ) -> Result<(), JsValue> {
+        self.ctx.begin_path();
+        self.ctx.set_stroke_style(&JsValue::from_str(color));
+        self.ctx
+            .ellipse(x, y, radius_x, radius_y, 0.0, 0.0, 2.0 * PI)?;
+        self.ctx.stroke();
+        self.ctx.close_path();
+        Ok(())
+    }
+
+    fn fill_text(
+        &mut self,
+        text: &str,
+        x: f64,
+        y: f64,
+        font: &str,
+        color: &str,
+    ) -> Result<(), JsValue> {
+        self.ctx.begin_path();
+        self.ctx.set_fill_style(&JsValue::from_str(color));
+        self.ctx.set_font(font);
+        self.ctx.fill_text(text, x, y)?;
+        self.ctx.close_path();
+        Ok(())
+    }
+}
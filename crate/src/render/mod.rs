@@ -0,0 +1,56 @@
+mod canvas;
+mod recording;
+mod transform;
+
+pub use canvas::CanvasRenderer;
+pub use recording::{DrawCommand, RecordingRenderer};
+pub use transform::Transform;
+
+pub use crate::types::SPRITES;
+
+use wasm_bindgen::prelude::*;
+
+use crate::types::Rect;
+
+/// A surface that the game can be drawn on
+pub trait Renderer {
+    /// Returns true if a sprite with the given name can be drawn
+    fn has_sprite(&self, name: &str) -> bool;
+
+    /// Set the opacity from 0 to 1 of everything drawn afterwards
+    fn set_alpha(&mut self, alpha: f64);
+
+    /// Draw a sprite into a Rect positioned relative to a Transform
+    fn draw_sprite(
+        &mut self,
+        name: &str,
+        transform: &Transform,
+        rect: &Rect,
+    ) -> Result<(), JsValue>;
+
+    /// Fill a Rect with a color
+    fn fill_rect(&mut self, rect: &Rect, color: &str) -> Result<(), JsValue>;
+
+    /// Outline a Rect with a color
+    fn stroke_rect(&mut self, rect: &Rect, color: &str) -> Result<(), JsValue>;
+
+    /// Outline an ellipse centered on (x, y) with a color
+    fn stroke_ellipse(
+        &mut self,
+        x: f64,
+        y: f64,
+        radius_x: f64,
+        radius_y: f64,
+        color: &str,
+    ) -> Result<(), JsValue>;
+
+    /// Write text starting at (x, y) in a font and color
+    fn fill_text(
+        &mut self,
+        text: &str,
+        x: f64,
+        y: f64,
+        font: &str,
+        color: &str,
+    ) -> Result<(), JsValue>;
+}
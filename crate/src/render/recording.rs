@@ -0,0 +1,155 @@
+use std::collections::HashSet;
+
+use wasm_bindgen::prelude::*;
+
+use super::{Renderer, Transform, SPRITES};
+use crate::types::Rect;
+
+/// A single call made to a Renderer
+#[derive(Debug, Clone, PartialEq)]
+pub enum DrawCommand {
+    Alpha {
+        alpha: f64,
+    },
+    Sprite {
+        name: String,
+        transform: Transform,
+        rect: Rect,
+    },
+    FillRect {
+        rect: Rect,
+        color: String,
+    },
+    StrokeRect {
+        rect: Rect,
+        color: String,
+    },
+    StrokeEllipse {
+        x: f64,
+        y: f64,
+        radius_x: f64,
+        radius_y: f64,
+        color: String,
+    },
+    Text {
+        text: String,
+        x: f64,
+        y: f64,
+        font: String,
+        color: String,
+    },
+}
+
+/// A Renderer that records every DrawCommand instead of drawing it
+#[derive(Debug)]
+pub struct RecordingRenderer {
+    sprites: HashSet<String>,
+    commands: Vec<DrawCommand>,
+}
+
+impl RecordingRenderer {
+    /// Construct a new RecordingRenderer that knows every sprite in `SPRITES`
+    pub fn new() -> Self {
+        Self::with_sprites(SPRITES)
+    }
+
+    /// Construct a new RecordingRenderer that only knows the named sprites
+    pub fn with_sprites(sprite_names: &[&str]) -> Self {
+        Self {
+            sprites: sprite_names.iter().map(|s| String::from(*s)).collect(),
+            commands: Vec::new(),
+        }
+    }
+
+    /// Returns the DrawCommands recorded so far
+    pub fn commands(&self) -> &[DrawCommand] {
+        &self.commands
+    }
+
+    /// Forget all recorded DrawCommands
+    pub fn clear(&mut self) {
+        self.commands.clear();
+    }
+}
+
+impl Default for RecordingRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Renderer for RecordingRenderer {
+    fn has_sprite(&self, name: &str) -> bool {
+        self.sprites.contains(name)
+    }
+
+    fn set_alpha(&mut self, alpha: f64) {
+        self.commands.push(DrawCommand::Alpha { alpha });
+    }
+
+    fn draw_sprite(
+        &mut self,
+        name: &str,
+        transform: &Transform,
+        rect: &Rect,
+    ) -> Result<(), JsValue> {
+        self.commands.push(DrawCommand::Sprite {
+            name: String::from(name),
+            transform: *transform,
+            rect: rect.clone(),
+        });
+        Ok(())
+    }
+
+    fn fill_rect(&mut self, rect: &Rect, color: &str) -> Result<(), JsValue> {
+        self.commands.push(DrawCommand::FillRect {
+            rect: rect.clone(),
+            color: String::from(color),
+        });
+        Ok(())
+    }
+
+    fn stroke_rect(&mut self, rect: &Rect, color: &str) -> Result<(), JsValue> {
+        self.commands.push(DrawCommand::StrokeRect {
+            rect: rect.clone(),
+            color: String::from(color),
+        });
+        Ok(())
+    }
+
+    fn stroke_ellipse(
+        &mut self,
+        x: f64,
+        y: f64,
+        radius_x: f64,
+        radius_y: f64,
+        color: &str,
+    ) -> Result<(), JsValue> {
+        self.commands.push(DrawCommand::StrokeEllipse {
+            x,
+            y,
+            radius_x,
+            radius_y,
+            color: String::from(color),
+        });
+        Ok(())
+    }
+
+    fn fill_text(
+        &mut self,
+        text: &str,
+        x: f64,
+        y: f64,
+        font: &str,
+        color: &str,
+    ) -> Result<(), JsValue> {
+        self.commands.push(DrawCommand::Text {
+            text: String::from(text),
+            x,
+            y,
+            font: String::from(font),
+            color: String::from(color),
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,38 @@
+/// A translation followed by a rotation applied to a sprite when it is drawn
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Transform {
+    x: f64,
+    y: f64,
+    rotation: f64,
+}
+
+impl Transform {
+    /// Construct a new Transform translating to (x, y) then rotating by `rotation` radians
+    pub fn new(x: f64, y: f64, rotation: f64) -> Self {
+        Self { x, y, rotation }
+    }
+
+    /// Construct a Transform that leaves a sprite where it is
+    pub fn identity() -> Self {
+        Self::new(0.0, 0.0, 0.0)
+    }
+
+    /// Returns the x translation
+    pub fn x(&self) -> f64 {
+        self.x
+    }
+    /// Returns the y translation
+    pub fn y(&self) -> f64 {
+        self.y
+    }
+    /// Returns the rotation in radians
+    pub fn rotation(&self) -> f64 {
+        self.rotation
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
@@ -0,0 +1,854 @@
+use std::{collections::HashSet, rc::Rc};
+
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    entity::{Burst, Button, PaperPlane, Projectile, Shot, Tower},
+    render::{Renderer, Transform},
+    types::{
+        ButtonType, Clock, Difficulty, Grid, HitPoints, Map, Mouse, PlacementError, PlaneChild,
+        PlanePath, PlaneRegistry, Rect, TowerRegistry, TowerStatus, WaveError, WaveModifiers,
+        WaveSchedule,
+    },
+};
+
+/// The default Map
+const MAP: &str = include_str!("../data/maps/backyard.json");
+/// The default PlaneRegistry
+const PLANES: &str = include_str!("../data/planes.json");
+/// The default TowerRegistry
+const TOWERS: &str = include_str!("../data/towers.json");
+/// The default WaveSchedule
+const WAVES: &str = include_str!("../data/waves.json");
+
+/// The cash the player starts with
+const STARTING_CASH: i32 = 10;
+/// The number of ticks feedback about a rejected placement is shown for
+const FEEDBACK_TICKS: u64 = 120;
+
+/// The player input gathered for a single step of the Simulation
+#[derive(Debug, Copy, Clone)]
+pub struct Input {
+    mouse_x: f64,
+    mouse_y: f64,
+    mouse_down: bool,
+    mouse_up: bool,
+}
+
+impl Input {
+    /// Construct a new Input from the mouse state
+    pub fn new(mouse_x: f64, mouse_y: f64, mouse_down: bool, mouse_up: bool) -> Self {
+        Self {
+            mouse_x,
+            mouse_y,
+            mouse_down,
+            mouse_up,
+        }
+    }
+}
+
+/// A Plane waiting to be spawned at a given tick
+struct Spawn {
+    tick: u64,
+    plane: String,
+    /// The index of the path the Plane takes
+    path: usize,
+    modifiers: WaveModifiers,
+}
+
+/// The browser-independent state and rules of a game
+pub struct Simulation {
+    plane_size: f64,
+    tower_size: f64,
+    ui_text_size: f64,
+
+    width: f64,
+    height: f64,
+    mouse: Mouse,
+
+    planes: Vec<PaperPlane>,
+    /// The id given to the next Plane spawned
+    next_plane_id: u64,
+    towers: Vec<Tower>,
+    projectiles: Vec<Projectile>,
+    bursts: Vec<Burst>,
+    buttons: Vec<Button>,
+    map: Map,
+    paths: Vec<Rc<PlanePath>>,
+    grid: Option<Grid>,
+    /// The cells of the Grid holding a Tower
+    occupied: HashSet<(i32, i32)>,
+    grid_overlay: bool,
+
+    plane_kinds: PlaneRegistry,
+    tower_kinds: TowerRegistry,
+    schedule: WaveSchedule,
+    spawns: Vec<Spawn>,
+    round: u32,
+    round_start_tick: u64,
+    clock: Clock,
+    hp: HitPoints,
+    cash: i32,
+    difficulty: Difficulty,
+    /// The last rejected placement and the tick its feedback expires
+    feedback: Option<(PlacementError, u64)>,
+}
+
+/// Returns the Map played when no other is chosen
+pub(crate) fn default_map() -> Map {
+    Map::from_json(MAP).unwrap_or_else(|e| panic!("{}", e))
+}
+
+impl Simulation {
+    /// Setup a new simulation of the default Map at the given size sending the default Waves
+    pub fn new(width: f64, height: f64) -> Self {
+        Self::with_map(width, height, default_map())
+    }
+
+    /// Setup a new simulation of the referenced Map at the given size sending the default
+    /// Waves
+    pub fn with_map(width: f64, height: f64, map: Map) -> Self {
+        let plane_kinds = PlaneRegistry::from_json(PLANES).unwrap_or_else(|e| panic!("{}", e));
+        let tower_kinds = TowerRegistry::from_json(TOWERS).unwrap_or_else(|e| panic!("{}", e));
+        let schedule = WaveSchedule::from_json(
+            WAVES,
+            |name| plane_kinds.contains(name),
+            |name| map.path(name).is_some(),
+        )
+        .unwrap_or_else(|e| panic!("{}", e));
+        Self::with_data(width, height, map, plane_kinds, tower_kinds, schedule)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Setup a new simulation of the referenced Map at the given size with the referenced
+    /// kinds of Planes and Towers and the Waves to send
+    ///
+    /// Fails if the Waves name a kind of Plane or a path the Map does not have.
+    pub fn with_data(
+        width: f64,
+        height: f64,
+        map: Map,
+        plane_kinds: PlaneRegistry,
+        tower_kinds: TowerRegistry,
+        schedule: WaveSchedule,
+    ) -> Result<Self, WaveError> {
+        schedule.validate_planes(|name| plane_kinds.contains(name))?;
+        schedule.validate_paths(|name| map.path(name).is_some())?;
+        let tower_size = height * 0.08;
+
+        // Create Tower Buttons
+        let buttons = tower_kinds
+            .families()
+            .iter()
+            .enumerate()
+            .map(|(i, family)| {
+                let n = (i + 1) as f64;
+                Button::new(
+                    Rect::new(
+                        width - 5.0 * n - tower_size * n,
+                        tower_size * 0.05,
+                        tower_size,
+                        tower_size,
+                    ),
+                    ButtonType::Build(i),
+                    family.tiers()[0].top(),
+                )
+            })
+            .collect();
+
+        let paths = map
+            .paths()
+            .iter()
+            .map(|path| Rc::new(path.to_plane_path(width, height)))
+            .collect();
+
+        let grid = map.grid().map(|grid| grid.to_grid(width, height));
+
+        Ok(Self {
+            map,
+            paths,
+            grid,
+            occupied: HashSet::new(),
+            grid_overlay: false,
+            plane_size: height * 0.05,
+            tower_size,
+            ui_text_size: height * 0.03,
+            width,
+            height,
+            mouse: Mouse::new(),
+            planes: Vec::with_capacity(50),
+            next_plane_id: 0,
+            towers: Vec::with_capacity(10),
+            projectiles: Vec::new(),
+            bursts: Vec::new(),
+            buttons,
+            plane_kinds,
+            tower_kinds,
+            schedule,
+            spawns: Vec::new(),
+            round: 1,
+            round_start_tick: 0,
+            clock: Clock::new(),
+            hp: HitPoints::new(100),
+            cash: STARTING_CASH,
+            difficulty: Difficulty::default(),
+            feedback: None,
+        })
+    }
+
+    /// Returns the width of the map
+    pub fn width(&self) -> f64 {
+        self.width
+    }
+    /// Returns the height of the map
+    pub fn height(&self) -> f64 {
+        self.height
+    }
+    /// Returns the size of a Tower
+    pub fn tower_size(&self) -> f64 {
+        self.tower_size
+    }
+
+    /// Returns the Clock of the Simulation
+    pub fn clock(&self) -> &Clock {
+        &self.clock
+    }
+
+    /// Returns the kinds of Planes that can be spawned
+    pub fn plane_kinds(&self) -> &PlaneRegistry {
+        &self.plane_kinds
+    }
+    /// Returns the kinds of Towers that can be built
+    pub fn tower_kinds(&self) -> &TowerRegistry {
+        &self.tower_kinds
+    }
+    /// Returns the WaveSchedule being sent
+    pub fn schedule(&self) -> &WaveSchedule {
+        &self.schedule
+    }
+    /// Returns the current round
+    pub fn round(&self) -> u32 {
+        self.round
+    }
+    /// Returns the player's HP
+    pub fn hp(&self) -> &HitPoints {
+        &self.hp
+    }
+    /// Returns the player's cash
+    pub fn cash(&self) -> i32 {
+        self.cash
+    }
+
+    /// Returns the Planes currently on the map
+    pub fn planes(&self) -> &[PaperPlane] {
+        &self.planes
+    }
+    /// Returns the first boss currently on the map, if any
+    pub fn boss(&self) -> Option<&PaperPlane> {
+        self.planes.iter().find(|plane| plane.kind().boss())
+    }
+    /// Returns the Towers currently on the map
+    pub fn towers(&self) -> &[Tower] {
+        &self.towers
+    }
+    /// Returns the Projectiles currently in flight
+    pub fn projectiles(&self) -> &[Projectile] {
+        &self.projectiles
+    }
+    /// Returns the Bursts currently shown
+    pub fn bursts(&self) -> &[Burst] {
+        &self.bursts
+    }
+    /// Returns the Buttons of the top bar
+    pub fn buttons(&self) -> &[Button] {
+        &self.buttons
+    }
+    /// Returns the Map being played
+    pub fn map(&self) -> &Map {
+        &self.map
+    }
+    /// Returns the paths of the Map scaled to the size of the Simulation
+    pub fn paths(&self) -> &[Rc<PlanePath>] {
+        &self.paths
+    }
+    /// Returns the Grid Towers snap to, if the Map has one
+    pub fn grid(&self) -> Option<&Grid> {
+        self.grid.as_ref()
+    }
+    /// Returns true if a Tower stands in the cell at the given column and row
+    pub fn is_occupied(&self, cell: (i32, i32)) -> bool {
+        self.occupied.contains(&cell)
+    }
+    /// Returns true if the Grid is drawn over the map
+    pub fn grid_overlay(&self) -> bool {
+        self.grid_overlay
+    }
+    /// Set whether the Grid is drawn over the map
+    pub fn set_grid_overlay(&mut self, grid_overlay: bool) {
+        self.grid_overlay = grid_overlay;
+    }
+
+    /// Returns the first path of the Map
+    pub fn path(&self) -> &PlanePath {
+        &self.paths[0]
+    }
+
+    /// Returns the Difficulty of the game
+    pub fn difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+    /// Set the Difficulty of the game, which also changes the sell price of every Tower
+    /// already built
+    pub fn set_difficulty(&mut self, difficulty: Difficulty) {
+        self.difficulty = difficulty;
+        for tower in self.towers.iter_mut() {
+            tower.set_sell_rate(difficulty.sell_rate());
+        }
+    }
+
+    /// Returns the reason the last placement was rejected while its feedback is shown
+    pub fn feedback(&self) -> Option<PlacementError> {
+        self.feedback
+            .filter(|&(_, expires)| self.clock.tick() < expires)
+            .map(|(error, _)| error)
+    }
+
+    /// Returns the height of the top bar
+    fn top_bar_height(&self) -> f64 {
+        self.tower_size * 1.2
+    }
+
+    /// Check whether a Tower could be placed at the referenced Rect
+    pub fn can_place(&self, rect: &Rect) -> Result<(), PlacementError> {
+        if !Rect::new(0.0, 0.0, self.width, self.height).contains(rect) {
+            return Err(PlacementError::OutOfBounds);
+        }
+        if rect.y() < self.top_bar_height() {
+            return Err(PlacementError::OverlapsUi);
+        }
+        if let Some(grid) = self.grid {
+            if self
+                .occupied
+                .contains(&grid.cell_at(rect.center_x(), rect.center_y()))
+            {
+                return Err(PlacementError::Occupied);
+            }
+        }
+        if self
+            .towers
+            .iter()
+            .any(|tower| tower.rect().intersects(rect))
+        {
+            return Err(PlacementError::OverlapsTower);
+        }
+        let clearance = self.map.clearance() * self.height;
+        if self
+            .paths
+            .iter()
+            .any(|path| path.distance_to_rect(rect) < clearance)
+        {
+            return Err(PlacementError::TooCloseToPath);
+        }
+        let (width, height) = (self.width, self.height);
+        if self
+            .map
+            .blocked()
+            .iter()
+            .any(|region| region.to_rect(width, height).intersects(rect))
+        {
+            return Err(PlacementError::Blocked);
+        }
+        let buildable = self.map.buildable();
+        if !buildable.is_empty()
+            && !buildable
+                .iter()
+                .any(|region| region.to_rect(width, height).contains(rect))
+        {
+            return Err(PlacementError::NotBuildable);
+        }
+        Ok(())
+    }
+
+    /// Returns true if the player has run out of HP
+    pub fn is_defeated(&self) -> bool {
+        self.hp.is_dead()
+    }
+
+    /// Returns true once every Wave and the boss have been sent and destroyed
+    pub fn is_victorious(&self) -> bool {
+        self.round as usize > self.schedule.rounds()
+            && self.spawns.is_empty()
+            && self.planes.is_empty()
+    }
+
+    /// Queue the Planes of the next Wave once its delay has passed
+    fn start_wave(&mut self) {
+        let wave = match self.schedule.waves().get(self.round as usize - 1) {
+            Some(wave) => wave,
+            None => return self.send_boss(),
+        };
+        if self.clock.since(self.round_start_tick) < wave.delay() {
+            return;
+        }
+
+        let now = self.clock.tick();
+        for group in wave.groups().iter() {
+            // the Planes of a group take each of its paths in turn, or every path of the Map
+            // if it lists none
+            let paths: Vec<usize> = if group.paths().is_empty() {
+                (0..self.paths.len()).collect()
+            } else {
+                group
+                    .paths()
+                    .iter()
+                    .map(|name| {
+                        self.map
+                            .paths()
+                            .iter()
+                            .position(|p| p.name() == name)
+                            .expect("with_data checks every path name against the Map")
+                    })
+                    .collect()
+            };
+            for (i, offset) in group.spawn_ticks().enumerate() {
+                self.spawns.push(Spawn {
+                    tick: now + offset,
+                    plane: String::from(group.plane()),
+                    path: paths[i % paths.len()],
+                    modifiers: *wave.modifiers(),
+                });
+            }
+        }
+        self.round_start_tick = now;
+        self.round += 1;
+    }
+
+    /// Queue the boss once every Wave has been sent and its delay has passed
+    fn send_boss(&mut self) {
+        let boss = match self.schedule.boss() {
+            Some(boss) if self.round as usize == self.schedule.rounds() => boss,
+            _ => return,
+        };
+        if self.clock.since(self.round_start_tick) < boss.delay() {
+            return;
+        }
+
+        let path = boss
+            .path()
+            .and_then(|name| self.map.paths().iter().position(|p| p.name() == name))
+            .unwrap_or(0);
+        let now = self.clock.tick();
+        self.spawns.push(Spawn {
+            tick: now,
+            plane: String::from(boss.plane()),
+            path,
+            modifiers: WaveModifiers::default(),
+        });
+        self.round_start_tick = now;
+        self.round += 1;
+    }
+
+    /// Spawn the queued Planes that are due
+    fn make_planes(&mut self) {
+        self.start_wave();
+
+        let now = self.clock.tick();
+        let (due, pending): (Vec<_>, Vec<_>) =
+            self.spawns.drain(..).partition(|spawn| spawn.tick <= now);
+        self.spawns = pending;
+
+        for spawn in due.iter() {
+            let kind = self
+                .plane_kinds
+                .get(&spawn.plane)
+                .expect("with_data checks every plane name against the Plane kinds");
+            let mut plane = PaperPlane::spawn(
+                self.next_plane_id,
+                kind,
+                &self.paths[spawn.path],
+                self.plane_size,
+            );
+            self.next_plane_id += 1;
+            plane.apply_modifiers(&spawn.modifiers);
+            self.planes.push(plane);
+        }
+    }
+
+    /// Returns the Rect a Tower placed at the mouse would cover, centered on the mouse's
+    /// cell if the Map has a Grid
+    fn tower_rect_at_mouse(&self) -> Rect {
+        let (x, y) = match self.grid {
+            Some(grid) => grid.snap(self.mouse.x(), self.mouse.y()),
+            None => (self.mouse.x(), self.mouse.y()),
+        };
+        Rect::new(
+            x - self.tower_size / 2.0,
+            y - self.tower_size / 2.0,
+            self.tower_size,
+            self.tower_size,
+        )
+    }
+
+    /// Place the Tower a top bar Button builds at the mouse if the placement is valid and the
+    /// player can afford it
+    fn place_tower(&mut self, button_type: ButtonType) {
+        let rect = self.tower_rect_at_mouse();
+        let family = match button_type {
+            ButtonType::Build(i) => match self.tower_kinds.families().get(i) {
+                Some(family) => family,
+                None => return,
+            },
+            _ => return,
+        };
+        if let Err(error) = self.can_place(&rect) {
+            self.feedback = Some((error, self.clock.tick() + FEEDBACK_TICKS));
+            return;
+        }
+        if self.cash >= family.cost() {
+            self.cash -= family.cost();
+            if let Some(grid) = self.grid {
+                self.occupied
+                    .insert(grid.cell_at(rect.center_x(), rect.center_y()));
+            }
+            let mut tower = Tower::build(family, rect, self.difficulty.sell_rate());
+            tower.place(&self.clock);
+            self.towers.push(tower);
+        }
+    }
+
+    /// Handle mouse events
+    fn events(&mut self) {
+        if self.mouse.up() {
+            for i in 0..self.buttons.len() {
+                if self.mouse.y() > self.top_bar_height() && self.buttons[i].selected() {
+                    self.place_tower(self.buttons[i].button_type());
+                }
+                let button = &mut self.buttons[i];
+                button.deselect();
+                if self.mouse.y() < self.tower_size && self.mouse.inside(button.rect()) {
+                    button.select();
+                }
+            }
+        }
+    }
+
+    /// Reveal camouflaged planes within range of a tower with detection
+    fn reveal_planes(&mut self) {
+        for plane in self.planes.iter_mut() {
+            let revealed = self
+                .towers
+                .iter()
+                .any(|tower| tower.detects() && tower.in_range(plane));
+            plane.set_revealed(revealed);
+        }
+    }
+
+    /// Handle tower interaction and harm planes
+    fn update_towers(&mut self) {
+        for tower in self.towers.iter_mut() {
+            tower.events(&self.mouse, &mut self.cash, &self.clock);
+            match tower.attack(&mut self.planes, &self.clock) {
+                Some(Shot::Projectile(projectile)) => self.projectiles.push(projectile),
+                Some(Shot::Burst(burst)) => self.bursts.push(burst),
+                None => (),
+            }
+        }
+    }
+
+    /// Move all planes along their paths
+    fn update_planes(&mut self) {
+        for plane in self.planes.iter_mut() {
+            plane.fly();
+        }
+    }
+
+    /// Move all projectiles and drop the ones that hit or fell, along with expired bursts
+    fn update_projectiles(&mut self) {
+        let clock = &self.clock;
+        self.bursts.retain(|burst| !burst.expired(clock));
+        for projectile in self.projectiles.iter_mut() {
+            if let Some(burst) = projectile.fly(&mut self.planes, &self.clock) {
+                self.bursts.push(burst);
+            }
+        }
+        self.projectiles.retain(|projectile| !projectile.spent());
+    }
+
+    /// Apply the effects Planes carry and spread them to the Planes around them
+    fn update_effects(&mut self) {
+        let mut spreads = Vec::new();
+        for plane in self.planes.iter_mut() {
+            for effect in plane.update_effects(&self.clock) {
+                spreads.push((plane.center_x(), plane.center_y(), effect));
+            }
+        }
+
+        // spread copies do not spread any further
+        for (x, y, effect) in spreads {
+            let radius = effect.spread().unwrap_or(0.0);
+            for plane in self.planes.iter_mut() {
+                let near = (plane.center_x() - x).hypot(plane.center_y() - y) <= radius;
+                if near && !plane.hp().is_dead() && !plane.has_effect(effect.kind().name()) {
+                    plane.afflict(effect.kind(), effect.damage_type(), None, &self.clock);
+                }
+            }
+        }
+    }
+
+    /// Begin the phases bosses have fallen into, spawning their escorts behind them
+    fn update_bosses(&mut self) {
+        let mut escorts = Vec::new();
+        for i in 0..self.planes.len() {
+            let children = self.planes[i].begin_phases(&self.clock);
+            if children.is_empty() {
+                continue;
+            }
+            let path = Rc::clone(self.planes[i].path());
+            let distance = self.planes[i].progress().distance();
+            let modifiers = *self.planes[i].modifiers();
+            escorts.extend(self.spawn_behind(&path, distance, &children, &modifiers));
+        }
+        self.planes.extend(escorts);
+    }
+
+    /// Returns new Planes of each of the referenced children with the referenced modifiers,
+    /// lined up behind each other from the given distance along the referenced path
+    ///
+    /// The line starts half a Plane short of the exit at the latest, so children of a Plane
+    /// destroyed as it left the path do not leak.
+    fn spawn_behind(
+        &mut self,
+        path: &Rc<PlanePath>,
+        distance: f64,
+        children: &[PlaneChild],
+        modifiers: &WaveModifiers,
+    ) -> Vec<PaperPlane> {
+        let distance = distance.min(path.length() - self.plane_size * 0.5);
+        let mut planes = Vec::new();
+        let mut behind = 0.0;
+        for child in children {
+            let kind = match self.plane_kinds.get(child.plane()) {
+                Some(kind) => Rc::clone(kind),
+                None => continue,
+            };
+            for _ in 0..child.count() {
+                let mut plane = PaperPlane::spawn_at(
+                    self.next_plane_id,
+                    &kind,
+                    path,
+                    self.plane_size,
+                    distance - behind,
+                );
+                plane.apply_modifiers(modifiers);
+                planes.push(plane);
+                self.next_plane_id += 1;
+                behind += self.plane_size * 0.5;
+            }
+        }
+        planes
+    }
+
+    /// Remove towers that have been deleted
+    fn remove_towers(&mut self) {
+        let (grid, occupied) = (self.grid, &mut self.occupied);
+        self.towers.retain(|tower| {
+            let deleted = matches!(tower.status(), TowerStatus::Deleted);
+            if let (true, Some(grid)) = (deleted, grid) {
+                occupied.remove(&grid.cell_at(tower.center_x(), tower.center_y()));
+            }
+            !deleted
+        });
+    }
+
+    /// Remove planes if they complete the track or get destroyed, spawning the children of
+    /// destroyed planes where they fell
+    fn remove_planes(&mut self) {
+        let mut children = Vec::new();
+        let mut i = 0;
+        while i != self.planes.len() {
+            if self.planes[i].hp().is_dead() {
+                let plane = self.planes.remove(i);
+                self.cash += plane.bounty() as i32;
+                children.extend(self.spawn_behind(
+                    plane.path(),
+                    plane.progress().distance(),
+                    plane.kind().children(),
+                    plane.modifiers(),
+                ));
+            } else if self.planes[i].finished() {
+                self.hp.take_damage(self.planes[i].damage());
+                self.planes.remove(i);
+            } else {
+                i += 1;
+            }
+        }
+        self.planes.extend(children);
+    }
+
+    /// Advance the Simulation by one tick
+    pub fn step(&mut self, input: &Input) {
+        self.mouse.update(
+            input.mouse_x,
+            input.mouse_y,
+            input.mouse_down,
+            input.mouse_up,
+        );
+
+        self.make_planes();
+
+        self.events();
+
+        self.reveal_planes();
+        self.update_towers();
+        self.update_planes();
+        self.update_projectiles();
+        self.update_effects();
+        self.update_bosses();
+
+        self.remove_towers();
+        self.remove_planes();
+
+        self.clock.advance();
+    }
+
+    /// Render text found in the top bar
+    fn draw_text(&self, renderer: &mut dyn Renderer) -> Result<(), JsValue> {
+        let font = format!("{}px sans-serif", self.ui_text_size);
+        renderer.fill_text(
+            &format!("❤️ : {}", self.hp.curr_hp()),
+            10.0,
+            self.ui_text_size + 5.0,
+            &font,
+            "#111111",
+        )?;
+        renderer.fill_text(
+            &format!("$  : {}", self.cash),
+            10.0,
+            self.tower_size - 5.0,
+            &font,
+            "#111111",
+        )?;
+        if let Some(error) = self.feedback() {
+            renderer.fill_text(
+                &error.to_string(),
+                self.width * 0.3,
+                self.ui_text_size + 5.0,
+                &font,
+                "#ff0000",
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Render the cells of the Grid, highlighting the occupied ones
+    fn draw_grid(&self, renderer: &mut dyn Renderer, grid: &Grid) -> Result<(), JsValue> {
+        let top = self.top_bar_height();
+        let area = Rect::new(0.0, top, self.width, self.height - top);
+        for cell in grid.cells_within(&area) {
+            let rect = grid.cell_rect(cell);
+            if self.occupied.contains(&cell) {
+                renderer.set_alpha(0.3);
+                renderer.fill_rect(&rect, "#ff0000")?;
+                renderer.set_alpha(1.0);
+            }
+            renderer.stroke_rect(&rect, "#ffffff")?;
+        }
+        Ok(())
+    }
+
+    /// Render a preview of the Tower the selected top bar Button would build at the mouse
+    fn draw_ghost(&self, renderer: &mut dyn Renderer) -> Result<(), JsValue> {
+        if self.mouse.y() <= self.top_bar_height() {
+            return Ok(());
+        }
+        let family = match self.buttons.iter().find(|button| button.selected()) {
+            Some(button) => match button.button_type() {
+                ButtonType::Build(i) => &self.tower_kinds.families()[i],
+                _ => return Ok(()),
+            },
+            None => return Ok(()),
+        };
+
+        let rect = self.tower_rect_at_mouse();
+        let color = if self.can_place(&rect).is_ok() && self.cash >= family.cost() {
+            "#00ff00"
+        } else {
+            "#ff0000"
+        };
+        Tower::draw_ghost(renderer, family, &rect, color)
+    }
+
+    /// Render the HP of the boss on the map, if any, across the top bar
+    fn draw_boss_bar(&self, renderer: &mut dyn Renderer) -> Result<(), JsValue> {
+        let boss = match self.boss() {
+            Some(boss) => boss,
+            None => return Ok(()),
+        };
+        let bar = Rect::new(
+            (self.width * 0.3).floor(),
+            (self.tower_size * 0.6).floor(),
+            (self.width * 0.35).floor(),
+            (self.tower_size * 0.4).floor(),
+        );
+        let color = if boss.shielded(&self.clock) {
+            "#00ffff"
+        } else {
+            "#ff0000"
+        };
+        renderer.fill_rect(&bar, "#333333")?;
+        renderer.fill_rect(
+            &Rect::new(
+                bar.x(),
+                bar.y(),
+                (bar.w() * boss.hp().percent()).floor(),
+                bar.h(),
+            ),
+            color,
+        )?;
+        renderer.stroke_rect(&bar, "#111111")?;
+        renderer.fill_text(
+            boss.kind().name(),
+            bar.x() + 5.0,
+            bar.y() + bar.h() * 0.8,
+            &format!("{}px sans-serif", bar.h() * 0.75),
+            "#ffffff",
+        )
+    }
+
+    /// Render the top bar
+    fn draw_top_bar(&self, renderer: &mut dyn Renderer) -> Result<(), JsValue> {
+        for button in self.buttons.iter() {
+            button.draw(renderer)?;
+        }
+        self.draw_boss_bar(renderer)?;
+        self.draw_text(renderer)
+    }
+
+    /// Render the map, its entities, and the top bar with the referenced Renderer
+    pub fn draw(&self, renderer: &mut dyn Renderer) -> Result<(), JsValue> {
+        renderer.draw_sprite(
+            self.map.background(),
+            &Transform::identity(),
+            &Rect::new(0.0, 0.0, self.width, self.height),
+        )?;
+        if let (true, Some(grid)) = (self.grid_overlay, &self.grid) {
+            self.draw_grid(renderer, grid)?;
+        }
+
+        for tower in self.towers.iter() {
+            tower.draw(renderer, &self.clock)?;
+        }
+        for plane in self.planes.iter() {
+            plane.draw(renderer, &self.clock)?;
+        }
+        for projectile in self.projectiles.iter() {
+            projectile.draw(renderer)?;
+        }
+        for burst in self.bursts.iter() {
+            burst.draw(renderer, &self.clock)?;
+        }
+        self.draw_ghost(renderer)?;
+
+        self.draw_top_bar(renderer)
+    }
+}
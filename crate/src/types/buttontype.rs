@@ -1,10 +1,12 @@
 /// A variant type denoting the purpose of a button
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum ButtonType {
-    WaterGun,
-    AcidTower,
-    SodaMaker,
-    Upgrade,
+    /// Builds the TowerFamily at the given index of the TowerRegistry
+    Build(usize),
+    /// Upgrades a Tower into the tier at the given index of its TowerFamily
+    Upgrade(usize),
     Delete,
+    /// Cycles the TargetMode of a Tower
+    Target,
     Other,
 }
@@ -0,0 +1,44 @@
+/// The number of milliseconds that pass in one tick of the game
+const TICK_MS: f64 = 1000.0 / 60.0;
+
+/// A fixed-timestep clock counting the ticks of a Simulation
+#[derive(Debug, Copy, Clone)]
+pub struct Clock {
+    tick: u64,
+    dt: f64,
+}
+
+impl Clock {
+    /// Construct a new Clock starting at tick 0
+    pub fn new() -> Self {
+        Self {
+            tick: 0,
+            dt: TICK_MS,
+        }
+    }
+
+    /// Returns the current tick
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+    /// Returns the fixed length of a tick in milliseconds
+    pub fn dt(&self) -> f64 {
+        self.dt
+    }
+
+    /// Returns the number of ticks that have passed since the given tick
+    pub fn since(&self, tick: u64) -> u64 {
+        self.tick.saturating_sub(tick)
+    }
+
+    /// Advance the Clock by one tick
+    pub fn advance(&mut self) {
+        self.tick += 1;
+    }
+}
+
+impl Default for Clock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
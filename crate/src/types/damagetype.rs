@@ -0,0 +1,23 @@
+use std::fmt;
+
+use serde::Deserialize;
+
+/// The kind of damage a Tower deals, which Planes may resist or be weak to
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DamageType {
+    #[default]
+    Water,
+    Acid,
+    Fizz,
+}
+
+impl fmt::Display for DamageType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DamageType::Water => write!(f, "water"),
+            DamageType::Acid => write!(f, "acid"),
+            DamageType::Fizz => write!(f, "fizz"),
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use std::str::FromStr;
+
+/// How forgiving a game is to the player
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    /// Returns the fraction of the cash invested in a Tower that selling it refunds
+    pub fn sell_rate(self) -> f64 {
+        match self {
+            Difficulty::Easy => 0.9,
+            Difficulty::Normal => 0.75,
+            Difficulty::Hard => 0.5,
+        }
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "easy" => Ok(Difficulty::Easy),
+            "normal" => Ok(Difficulty::Normal),
+            "hard" => Ok(Difficulty::Hard),
+            _ => Err(format!("unknown difficulty \"{}\"", s)),
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use super::Rect;
+
+/// A square grid of cells in pixels that Towers snap to
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Grid {
+    cell: f64,
+    offset_x: f64,
+    offset_y: f64,
+}
+
+impl Grid {
+    /// Construct a new Grid of cells of the given size whose first cell starts at the offset
+    pub fn new(cell: f64, offset_x: f64, offset_y: f64) -> Self {
+        Self {
+            cell,
+            offset_x,
+            offset_y,
+        }
+    }
+
+    /// Returns the size of a cell
+    pub fn cell(&self) -> f64 {
+        self.cell
+    }
+
+    /// Returns the column and row of the cell containing (x, y)
+    pub fn cell_at(&self, x: f64, y: f64) -> (i32, i32) {
+        (
+            ((x - self.offset_x) / self.cell).floor() as i32,
+            ((y - self.offset_y) / self.cell).floor() as i32,
+        )
+    }
+
+    /// Returns the Rect covered by the cell at the given column and row
+    pub fn cell_rect(&self, (col, row): (i32, i32)) -> Rect {
+        Rect::new(
+            self.offset_x + col as f64 * self.cell,
+            self.offset_y + row as f64 * self.cell,
+            self.cell,
+            self.cell,
+        )
+    }
+
+    /// Returns the center of the cell containing (x, y)
+    pub fn snap(&self, x: f64, y: f64) -> (f64, f64) {
+        let rect = self.cell_rect(self.cell_at(x, y));
+        (rect.center_x(), rect.center_y())
+    }
+
+    /// Returns every cell that lies entirely within the referenced Rect
+    pub fn cells_within(&self, rect: &Rect) -> Vec<(i32, i32)> {
+        let (first_col, first_row) = self.cell_at(rect.x(), rect.y());
+        let (last_col, last_row) = self.cell_at(rect.x() + rect.w(), rect.y() + rect.h());
+        (first_row..=last_row)
+            .flat_map(|row| (first_col..=last_col).map(move |col| (col, row)))
+            .filter(|&cell| rect.contains(&self.cell_rect(cell)))
+            .collect()
+    }
+}
@@ -0,0 +1,62 @@
+use super::{DamageType, EffectKind};
+
+/// The damage and effects one shot of a Tower deals to each Plane it reaches
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hit {
+    damage: u32,
+    damage_type: DamageType,
+    effects: Vec<EffectKind>,
+    /// The size of the Tower that fired, scaling the radii of the effects
+    tower_size: f64,
+    /// Whether the Tower that fired can see camouflaged Planes
+    detection: bool,
+}
+
+impl Hit {
+    /// Construct a new Hit dealing the given damage of the given type and effects from a Tower
+    /// of the given size, with or without detection
+    pub fn new(
+        damage: u32,
+        damage_type: DamageType,
+        effects: &[EffectKind],
+        tower_size: f64,
+        detection: bool,
+    ) -> Self {
+        Self {
+            damage,
+            damage_type,
+            effects: effects.to_vec(),
+            tower_size,
+            detection,
+        }
+    }
+
+    /// Returns the damage dealt
+    pub fn damage(&self) -> u32 {
+        self.damage
+    }
+    /// Returns the type of the damage and of the damage the effects deal
+    pub fn damage_type(&self) -> DamageType {
+        self.damage_type
+    }
+    /// Returns the effects left on the Planes hit
+    pub fn effects(&self) -> &[EffectKind] {
+        &self.effects
+    }
+    /// Returns the size of the Tower that fired
+    pub fn tower_size(&self) -> f64 {
+        self.tower_size
+    }
+    /// Returns true if the Hit reaches camouflaged Planes that are not revealed
+    pub fn detection(&self) -> bool {
+        self.detection
+    }
+
+    /// Returns the Hit with its damage scaled by the given factor
+    pub fn scaled(&self, factor: f64) -> Self {
+        Self {
+            damage: (self.damage as f64 * factor).round() as u32,
+            ..self.clone()
+        }
+    }
+}
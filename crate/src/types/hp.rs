@@ -1,4 +1,4 @@
-///
+/// The health of a Plane or of the player
 pub struct HitPoints {
     hp: u32,
     max_hp: u32,
@@ -14,6 +14,10 @@ impl HitPoints {
     pub fn curr_hp(&self) -> u32 {
         self.hp
     }
+    /// Return the maximum HP
+    pub fn max_hp(&self) -> u32 {
+        self.max_hp
+    }
     /// Return the current HP as a percentage
     pub fn percent(&self) -> f64 {
         self.hp as f64 / self.max_hp as f64
@@ -26,10 +30,6 @@ impl HitPoints {
 
     /// Reduce the HP by a damage value
     pub fn take_damage(&mut self, dmg: u32) {
-        self.hp = if let Some(hp) = self.hp.checked_sub(dmg) {
-            hp
-        } else {
-            0
-        };
+        self.hp = self.hp.saturating_sub(dmg);
     }
 }
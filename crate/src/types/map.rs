@@ -0,0 +1,278 @@
+use std::{collections::HashSet, error::Error, fmt};
+
+use serde::Deserialize;
+
+use super::{Grid, PlanePath, Rect, SPRITES};
+
+/// A rectangular area of a Map in coordinates normalized to its size
+#[derive(Debug, Copy, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Region {
+    x: f64,
+    y: f64,
+    w: f64,
+    h: f64,
+}
+
+impl Region {
+    /// Returns the Rect the Region covers on a map of the given size
+    pub fn to_rect(&self, width: f64, height: f64) -> Rect {
+        Rect::new(
+            self.x * width,
+            self.y * height,
+            self.w * width,
+            self.h * height,
+        )
+    }
+}
+
+/// The smallest cell a grid may have, as a fraction of the map's height
+const MIN_CELL: f64 = 0.02;
+
+/// The size and offset of the grid Towers snap to in normalized coordinates
+#[derive(Debug, Copy, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MapGrid {
+    /// The size of a cell as a fraction of the map's height
+    cell: f64,
+    #[serde(default)]
+    offset: (f64, f64),
+}
+
+impl MapGrid {
+    /// Returns the Grid covering a map of the given size
+    pub fn to_grid(&self, width: f64, height: f64) -> Grid {
+        Grid::new(
+            self.cell * height,
+            self.offset.0 * width,
+            self.offset.1 * height,
+        )
+    }
+}
+
+/// A named route from a spawn point to an exit point in normalized coordinates
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MapPath {
+    name: String,
+    spawn: (f64, f64),
+    #[serde(default)]
+    points: Vec<(f64, f64)>,
+    exit: (f64, f64),
+}
+
+impl MapPath {
+    /// Returns the name of the path
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    /// Returns the point where Planes spawn
+    pub fn spawn(&self) -> (f64, f64) {
+        self.spawn
+    }
+    /// Returns the points Planes fly through between the spawn and the exit
+    pub fn points(&self) -> &[(f64, f64)] {
+        &self.points
+    }
+    /// Returns the point where Planes leave the map
+    pub fn exit(&self) -> (f64, f64) {
+        self.exit
+    }
+
+    /// Returns the PlanePath the path covers on a map of the given size
+    pub fn to_plane_path(&self, width: f64, height: f64) -> PlanePath {
+        let points = std::iter::once(&self.spawn)
+            .chain(self.points.iter())
+            .chain(std::iter::once(&self.exit))
+            .map(|&(x, y)| (x * width, y * height))
+            .collect();
+        PlanePath::new(points)
+    }
+}
+
+/// An error found while loading a Map
+#[derive(Debug)]
+pub enum MapError {
+    /// The document is not valid JSON or does not have the expected shape
+    Parse(serde_json::Error),
+    /// The background is not a known sprite
+    UnknownSprite(String),
+    /// The map does not have any paths
+    NoPaths,
+    /// Two paths share the same name
+    DuplicatePath(String),
+    /// A path has a point that is not a finite number
+    InvalidPoint { path: String },
+    /// A region does not cover a positive finite area
+    InvalidRegion { kind: &'static str, region: usize },
+    /// The clearance is negative or not a finite number
+    InvalidClearance,
+    /// The grid has a cell size below `MIN_CELL` or that is not finite, or an offset that is
+    /// not finite
+    InvalidGrid,
+}
+
+impl fmt::Display for MapError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MapError::Parse(e) => write!(f, "invalid map document: {}", e),
+            MapError::UnknownSprite(name) => write!(f, "unknown background sprite \"{}\"", name),
+            MapError::NoPaths => write!(f, "the map does not have any paths"),
+            MapError::DuplicatePath(name) => {
+                write!(f, "path \"{}\" is defined more than once", name)
+            }
+            MapError::InvalidPoint { path } => {
+                write!(f, "path \"{}\": points must be finite numbers", path)
+            }
+            MapError::InvalidRegion { kind, region } => write!(
+                f,
+                "{} region {}: width and height must be positive numbers",
+                kind, region
+            ),
+            MapError::InvalidClearance => {
+                write!(f, "the clearance must be a finite number of at least 0")
+            }
+            MapError::InvalidGrid => write!(
+                f,
+                "the grid must have a cell size of at least {} and a finite offset",
+                MIN_CELL
+            ),
+        }
+    }
+}
+
+impl Error for MapError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            MapError::Parse(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for MapError {
+    fn from(e: serde_json::Error) -> Self {
+        MapError::Parse(e)
+    }
+}
+
+/// A level: its background, the paths Planes fly, and where Towers may be built
+///
+/// Coordinates are normalized so that the map fits a canvas of any size.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Map {
+    name: String,
+    background: String,
+    paths: Vec<MapPath>,
+    #[serde(default)]
+    blocked: Vec<Region>,
+    #[serde(default)]
+    buildable: Vec<Region>,
+    #[serde(default = "default_clearance")]
+    clearance: f64,
+    #[serde(default)]
+    grid: Option<MapGrid>,
+}
+
+/// The distance Towers are kept from paths, as a fraction of the map's height, if a Map does
+/// not set one
+fn default_clearance() -> f64 {
+    0.03
+}
+
+impl Map {
+    /// Parse and validate a Map from a JSON document
+    ///
+    /// Regions in errors are numbered from 1.
+    pub fn from_json(json: &str) -> Result<Self, MapError> {
+        let map: Self = serde_json::from_str(json)?;
+        map.validate()?;
+        Ok(map)
+    }
+
+    fn validate(&self) -> Result<(), MapError> {
+        if !SPRITES.contains(&self.background.as_str()) {
+            return Err(MapError::UnknownSprite(self.background.clone()));
+        }
+        if self.paths.is_empty() {
+            return Err(MapError::NoPaths);
+        }
+        let mut names = HashSet::new();
+        for path in self.paths.iter() {
+            if !names.insert(path.name.as_str()) {
+                return Err(MapError::DuplicatePath(path.name.clone()));
+            }
+            let finite = std::iter::once(&path.spawn)
+                .chain(path.points.iter())
+                .chain(std::iter::once(&path.exit))
+                .all(|(x, y)| x.is_finite() && y.is_finite());
+            if !finite {
+                return Err(MapError::InvalidPoint {
+                    path: path.name.clone(),
+                });
+            }
+        }
+        if !self.clearance.is_finite() || self.clearance < 0.0 {
+            return Err(MapError::InvalidClearance);
+        }
+        if let Some(grid) = &self.grid {
+            let valid = grid.cell.is_finite()
+                && grid.cell >= MIN_CELL
+                && grid.offset.0.is_finite()
+                && grid.offset.1.is_finite();
+            if !valid {
+                return Err(MapError::InvalidGrid);
+            }
+        }
+        for (kind, regions) in [("blocked", &self.blocked), ("buildable", &self.buildable)] {
+            for (i, region) in regions.iter().enumerate() {
+                let valid = [region.x, region.y, region.w, region.h]
+                    .iter()
+                    .all(|v| v.is_finite())
+                    && region.w > 0.0
+                    && region.h > 0.0;
+                if !valid {
+                    return Err(MapError::InvalidRegion {
+                        kind,
+                        region: i + 1,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the name of the map
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    /// Returns the name of the sprite drawn behind everything else
+    pub fn background(&self) -> &str {
+        &self.background
+    }
+    /// Returns every path of the map
+    pub fn paths(&self) -> &[MapPath] {
+        &self.paths
+    }
+    /// Returns the path with the given name
+    pub fn path(&self, name: &str) -> Option<&MapPath> {
+        self.paths.iter().find(|path| path.name == name)
+    }
+    /// Returns the regions where Towers may never be built
+    pub fn blocked(&self) -> &[Region] {
+        &self.blocked
+    }
+    /// Returns the regions Towers must be built within, or none if Towers may be built anywhere
+    pub fn buildable(&self) -> &[Region] {
+        &self.buildable
+    }
+    /// Returns the distance Towers are kept from paths as a fraction of the map's height
+    pub fn clearance(&self) -> f64 {
+        self.clearance
+    }
+    /// Returns the grid Towers snap to, if the map has one
+    pub fn grid(&self) -> Option<&MapGrid> {
+        self.grid.as_ref()
+    }
+}
@@ -1,20 +1,66 @@
 mod buttontype;
+mod clock;
+mod damagetype;
+mod difficulty;
+mod grid;
+mod hit;
 mod hp;
+mod map;
 mod mouse;
+mod pathprogress;
+mod placement;
+mod planekind;
 mod planepath;
+mod planetrait;
+mod projectilekind;
 mod rect;
+mod splash;
+mod sprites;
+mod statuseffect;
+mod targetmode;
+mod towerkind;
 mod towerstatus;
-mod towertype;
+mod wave;
 
 pub use buttontype::ButtonType;
+pub use clock::Clock;
+pub use damagetype::DamageType;
+pub use difficulty::Difficulty;
+pub use grid::Grid;
+pub use hit::Hit;
 pub use hp::HitPoints;
+pub use map::Map;
+pub use map::MapError;
+pub use map::MapGrid;
+pub use map::MapPath;
+pub use map::Region;
 pub use mouse::Mouse;
-pub use planepath::Direction;
+pub use pathprogress::PathProgress;
+pub use placement::PlacementError;
+pub use planekind::BossPhase;
+pub use planekind::PlaneChild;
+pub use planekind::PlaneKind;
+pub use planekind::PlaneKindError;
+pub use planekind::PlaneRegistry;
 pub use planepath::PlanePath;
-pub use planepath::Turn;
+pub use planetrait::PlaneTrait;
+pub use projectilekind::ProjectileKind;
 pub use rect::Rect;
+pub use splash::Splash;
+pub use sprites::SPRITES;
+pub use statuseffect::EffectKind;
+pub use statuseffect::StatusEffect;
+pub use targetmode::TargetMode;
+pub use towerkind::TowerFamily;
+pub use towerkind::TowerKindError;
+pub use towerkind::TowerRegistry;
+pub use towerkind::TowerTier;
 pub use towerstatus::TowerStatus;
-pub use towertype::AcidTower;
-pub use towertype::SodaMaker;
-pub use towertype::TowerType;
-pub use towertype::WaterGun;
+pub use wave::Wave;
+pub use wave::WaveBoss;
+pub use wave::WaveError;
+pub use wave::WaveGroup;
+pub use wave::WaveModifiers;
+pub use wave::WaveSchedule;
+
+pub(crate) use planepath::point_to_segment;
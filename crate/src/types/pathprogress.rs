@@ -0,0 +1,33 @@
+/// How far a Plane has made it along its PlanePath
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct PathProgress {
+    distance: f64,
+    segment: usize,
+}
+
+impl PathProgress {
+    /// Construct a new PathProgress at the start of a path
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the distance travelled along the path in pixels
+    pub fn distance(&self) -> f64 {
+        self.distance
+    }
+    /// Returns the index of the segment of the path currently being travelled
+    ///
+    /// Segment `n` leads from point `n` of the path to point `n + 1`.
+    pub fn segment(&self) -> usize {
+        self.segment
+    }
+
+    /// Add the referenced distance to the distance travelled
+    pub fn advance(&mut self, distance: f64) {
+        self.distance += distance;
+    }
+    /// Move onto the segment with the given index
+    pub fn enter_segment(&mut self, segment: usize) {
+        self.segment = segment;
+    }
+}
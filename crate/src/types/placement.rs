@@ -0,0 +1,35 @@
+use std::fmt;
+
+/// A reason a Tower cannot be placed where the player asked
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum PlacementError {
+    /// The Tower would hang off the edge of the map
+    OutOfBounds,
+    /// The Tower would cover the top bar
+    OverlapsUi,
+    /// The Tower would go in a cell of the Grid that already holds a Tower
+    Occupied,
+    /// The Tower would overlap another Tower
+    OverlapsTower,
+    /// The Tower would be within the clearance of a path
+    TooCloseToPath,
+    /// The Tower would overlap a blocked region of the Map
+    Blocked,
+    /// The Tower would not lie within a buildable region of the Map
+    NotBuildable,
+}
+
+impl fmt::Display for PlacementError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PlacementError::OutOfBounds => write!(f, "Towers must be placed on the map"),
+            PlacementError::OverlapsUi => write!(f, "Towers cannot cover the top bar"),
+            PlacementError::Occupied => write!(f, "That cell already holds a Tower"),
+            PlacementError::OverlapsTower => write!(f, "Towers cannot overlap"),
+            PlacementError::TooCloseToPath => write!(f, "Too close to the path"),
+            PlacementError::Blocked | PlacementError::NotBuildable => {
+                write!(f, "Towers cannot be built here")
+            }
+        }
+    }
+}
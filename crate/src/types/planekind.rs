@@ -0,0 +1,344 @@
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    fmt,
+    rc::Rc,
+};
+
+use serde::Deserialize;
+
+use super::{DamageType, PlaneTrait, SPRITES};
+
+/// Planes of one kind spawned where a Plane is destroyed or a boss begins a phase
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PlaneChild {
+    plane: String,
+    count: u32,
+}
+
+impl PlaneChild {
+    /// Returns the name of the kind of Plane spawned
+    pub fn plane(&self) -> &str {
+        &self.plane
+    }
+    /// Returns the number of Planes spawned
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+}
+
+/// A stage of a boss fight that begins once the boss falls to a share of its HP
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BossPhase {
+    hp: f64,
+    #[serde(default = "default_phase_speed")]
+    speed: f64,
+    #[serde(default)]
+    shield: u64,
+    #[serde(default)]
+    escorts: Vec<PlaneChild>,
+}
+
+/// The speed multiplier of a BossPhase that does not set one
+fn default_phase_speed() -> f64 {
+    1.0
+}
+
+impl BossPhase {
+    /// Returns the share of its HP the boss falls to when the phase begins
+    pub fn hp(&self) -> f64 {
+        self.hp
+    }
+    /// Returns the multiplier applied to the boss's speed during the phase
+    pub fn speed(&self) -> f64 {
+        self.speed
+    }
+    /// Returns the number of ticks the boss takes no damage for when the phase begins
+    pub fn shield(&self) -> u64 {
+        self.shield
+    }
+    /// Returns the Planes spawned behind the boss when the phase begins
+    pub fn escorts(&self) -> &[PlaneChild] {
+        &self.escorts
+    }
+}
+
+/// The stats shared by every Plane of one kind
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PlaneKind {
+    name: String,
+    sprite: String,
+    speed: f64,
+    hp: u32,
+    damage: u32,
+    bounty: u32,
+    #[serde(default)]
+    armor: u32,
+    #[serde(default)]
+    resistances: HashMap<DamageType, f64>,
+    #[serde(default)]
+    children: Vec<PlaneChild>,
+    #[serde(default)]
+    boss: bool,
+    #[serde(default)]
+    phases: Vec<BossPhase>,
+    #[serde(default)]
+    traits: Vec<PlaneTrait>,
+}
+
+impl PlaneKind {
+    /// Returns the name that waves refer to the kind by
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    /// Returns the name of the sprite drawn for the kind
+    pub fn sprite(&self) -> &str {
+        &self.sprite
+    }
+    /// Returns the distance travelled in pixels per tick
+    pub fn speed(&self) -> f64 {
+        self.speed
+    }
+    /// Returns the starting HP
+    pub fn hp(&self) -> u32 {
+        self.hp
+    }
+    /// Returns the damage done to the player upon course completion
+    pub fn damage(&self) -> u32 {
+        self.damage
+    }
+    /// Returns the cash yielded when destroyed
+    pub fn bounty(&self) -> u32 {
+        self.bounty
+    }
+    /// Returns the damage taken off of every hit
+    pub fn armor(&self) -> u32 {
+        self.armor
+    }
+    /// Returns the share of damage of the given type resisted, where 1 is immune and a
+    /// negative share is a weakness
+    pub fn resistance(&self, damage_type: DamageType) -> f64 {
+        self.resistances.get(&damage_type).copied().unwrap_or(0.0)
+    }
+
+    /// Returns the Planes spawned when a Plane of the kind is destroyed
+    pub fn children(&self) -> &[PlaneChild] {
+        &self.children
+    }
+    /// Returns the names of the kinds of every Plane spawned by Planes of the kind, when
+    /// destroyed or as escorts
+    fn spawns(&self) -> impl Iterator<Item = &str> {
+        self.children
+            .iter()
+            .chain(self.phases.iter().flat_map(|phase| phase.escorts.iter()))
+            .map(PlaneChild::plane)
+    }
+
+    /// Returns true if Planes of the kind are bosses, whose HP is shown in the top bar
+    pub fn boss(&self) -> bool {
+        self.boss
+    }
+    /// Returns the phases of a boss in the order they begin
+    pub fn phases(&self) -> &[BossPhase] {
+        &self.phases
+    }
+    /// Returns the optional traits of the kind
+    pub fn traits(&self) -> &[PlaneTrait] {
+        &self.traits
+    }
+    /// Returns true if the kind has the given trait
+    pub fn has_trait(&self, plane_trait: PlaneTrait) -> bool {
+        self.traits.contains(&plane_trait)
+    }
+}
+
+/// An error found while loading a PlaneRegistry
+#[derive(Debug)]
+pub enum PlaneKindError {
+    /// The document is not valid JSON or does not have the expected shape
+    Parse(serde_json::Error),
+    /// Two kinds share the same name
+    Duplicate(String),
+    /// A kind is drawn with a sprite that does not exist
+    UnknownSprite { plane: String, sprite: String },
+    /// A kind has a stat that would make it unplayable
+    InvalidStat { plane: String, stat: &'static str },
+    /// A kind resists more than all of a type of damage or a share that is not a finite number
+    InvalidResistance {
+        plane: String,
+        damage_type: DamageType,
+    },
+    /// A kind spawns children or escorts of a kind that does not exist
+    UnknownChild { plane: String, child: String },
+    /// A kind spawns children or escorts that in turn spawn the kind again
+    ChildCycle(String),
+    /// A boss phase begins at a share of HP outside of 0 and 1 or not below the phase before it
+    InvalidPhase { plane: String, phase: usize },
+    /// A kind has phases without being a boss
+    NotABoss(String),
+}
+
+impl fmt::Display for PlaneKindError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PlaneKindError::Parse(e) => write!(f, "invalid plane document: {}", e),
+            PlaneKindError::Duplicate(name) => {
+                write!(f, "plane kind \"{}\" is defined more than once", name)
+            }
+            PlaneKindError::UnknownSprite { plane, sprite } => {
+                write!(f, "plane kind \"{}\": unknown sprite \"{}\"", plane, sprite)
+            }
+            PlaneKindError::InvalidStat { plane, stat } => write!(
+                f,
+                "plane kind \"{}\": {} must be a positive number",
+                plane, stat
+            ),
+            PlaneKindError::InvalidResistance { plane, damage_type } => write!(
+                f,
+                "plane kind \"{}\": the resistance to {} must be a finite number of at most 1",
+                plane, damage_type
+            ),
+            PlaneKindError::UnknownChild { plane, child } => write!(
+                f,
+                "plane kind \"{}\": unknown child plane \"{}\"",
+                plane, child
+            ),
+            PlaneKindError::ChildCycle(name) => {
+                write!(f, "plane kind \"{}\" spawns planes of its own kind", name)
+            }
+            PlaneKindError::InvalidPhase { plane, phase } => write!(
+                f,
+                "plane kind \"{}\": phase {} must begin at a share of HP between 0 and 1 below \
+                 the phase before it",
+                plane, phase
+            ),
+            PlaneKindError::NotABoss(name) => {
+                write!(f, "plane kind \"{}\" has phases but is not a boss", name)
+            }
+        }
+    }
+}
+
+impl Error for PlaneKindError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            PlaneKindError::Parse(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for PlaneKindError {
+    fn from(e: serde_json::Error) -> Self {
+        PlaneKindError::Parse(e)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PlaneDocument {
+    planes: Vec<PlaneKind>,
+}
+
+/// Every PlaneKind that can be spawned, looked up by name
+#[derive(Debug, Clone, Default)]
+pub struct PlaneRegistry {
+    kinds: HashMap<String, Rc<PlaneKind>>,
+}
+
+impl PlaneRegistry {
+    /// Parse and validate a PlaneRegistry from a JSON document
+    pub fn from_json(json: &str) -> Result<Self, PlaneKindError> {
+        let document: PlaneDocument = serde_json::from_str(json)?;
+
+        let mut kinds = HashMap::new();
+        for kind in document.planes.into_iter() {
+            let invalid = |stat| PlaneKindError::InvalidStat {
+                plane: kind.name.clone(),
+                stat,
+            };
+            if !SPRITES.contains(&kind.sprite.as_str()) {
+                return Err(PlaneKindError::UnknownSprite {
+                    plane: kind.name.clone(),
+                    sprite: kind.sprite.clone(),
+                });
+            }
+            if !kind.speed.is_finite() || kind.speed <= 0.0 {
+                return Err(invalid("speed"));
+            }
+            if kind.hp == 0 {
+                return Err(invalid("hp"));
+            }
+            for (&damage_type, &share) in kind.resistances.iter() {
+                if !share.is_finite() || share > 1.0 {
+                    return Err(PlaneKindError::InvalidResistance {
+                        plane: kind.name.clone(),
+                        damage_type,
+                    });
+                }
+            }
+            if !kind.boss && !kind.phases.is_empty() {
+                return Err(PlaneKindError::NotABoss(kind.name));
+            }
+            let mut above = 1.0;
+            for (p, phase) in kind.phases.iter().enumerate() {
+                if !(phase.hp > 0.0 && phase.hp < above) {
+                    return Err(PlaneKindError::InvalidPhase {
+                        plane: kind.name.clone(),
+                        phase: p + 1,
+                    });
+                }
+                if !phase.speed.is_finite() || phase.speed <= 0.0 {
+                    return Err(invalid("phase speed"));
+                }
+                above = phase.hp;
+            }
+            if kinds.contains_key(&kind.name) {
+                return Err(PlaneKindError::Duplicate(kind.name));
+            }
+            kinds.insert(kind.name.clone(), Rc::new(kind));
+        }
+
+        let registry = Self { kinds };
+        registry.validate_children()?;
+        Ok(registry)
+    }
+
+    /// Check that every child and escort is a known kind and that no kind spawns itself
+    fn validate_children(&self) -> Result<(), PlaneKindError> {
+        for kind in self.kinds.values() {
+            if let Some(child) = kind.spawns().find(|child| !self.contains(child)) {
+                return Err(PlaneKindError::UnknownChild {
+                    plane: kind.name.clone(),
+                    child: String::from(child),
+                });
+            }
+        }
+
+        for kind in self.kinds.values() {
+            let mut seen = HashSet::new();
+            let mut pending: Vec<&str> = kind.spawns().collect();
+            while let Some(name) = pending.pop() {
+                if name == kind.name {
+                    return Err(PlaneKindError::ChildCycle(kind.name.clone()));
+                }
+                if seen.insert(name) {
+                    pending.extend(self.kinds[name].spawns());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the PlaneKind with the given name
+    pub fn get(&self, name: &str) -> Option<&Rc<PlaneKind>> {
+        self.kinds.get(name)
+    }
+    /// Returns true if a PlaneKind with the given name exists
+    pub fn contains(&self, name: &str) -> bool {
+        self.kinds.contains_key(name)
+    }
+}
@@ -1,71 +1,133 @@
 use super::Rect;
 
-/// The four different directions a Turn can point
-#[derive(Debug, Copy, Clone)]
-pub enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
+/// A polyline of points along which the center of a Plane flies
+pub struct PlanePath {
+    points: Vec<(f64, f64)>,
+    /// The distance along the path at which each point is reached
+    distances: Vec<f64>,
 }
 
-/// A vertice along a PlanePath
-pub struct Turn {
-    x: f64,
-    y: f64,
-    direction: Direction,
-}
+impl PlanePath {
+    /// Construct a new PlanePath through the given points
+    ///
+    /// Panics if fewer than two points are given.
+    pub fn new(points: Vec<(f64, f64)>) -> Self {
+        assert!(points.len() >= 2, "a PlanePath needs at least two points");
 
-impl Turn {
-    /// Construct a new Turn
-    pub fn new(pos: (f64, f64), direction: Direction) -> Self {
-        Self {
-            x: pos.0,
-            y: pos.1,
-            direction,
+        let mut distances = Vec::with_capacity(points.len());
+        distances.push(0.0);
+        for pair in points.windows(2) {
+            let (x0, y0) = pair[0];
+            let (x1, y1) = pair[1];
+            let last = distances[distances.len() - 1];
+            distances.push(last + (x1 - x0).hypot(y1 - y0));
         }
+
+        Self { points, distances }
     }
 
-    /// Returns the Direction that the Turn points
-    pub fn direction(&self) -> Direction {
-        self.direction
+    /// Returns a reference to the points of the path
+    pub fn points(&self) -> &[(f64, f64)] {
+        &self.points
+    }
+    /// Returns the number of segments between the points of the path
+    pub fn segments(&self) -> usize {
+        self.points.len() - 1
+    }
+    /// Returns the total length of the path
+    pub fn length(&self) -> f64 {
+        self.distances[self.distances.len() - 1]
     }
 
-    /// Returns true if the referenced Rect passes into the turn
-    pub fn touching(&self, rect: &Rect) -> bool {
-        rect.x() > self.x - 10.0
-            && rect.x() < self.x + 10.0
-            && rect.y() > self.y - 10.0
-            && rect.y() < self.y + 10.0
+    /// Returns the index of the segment found at the given distance along the path
+    pub fn segment_at(&self, distance: f64) -> usize {
+        self.distances[1..self.segments()]
+            .iter()
+            .take_while(|&&start| start <= distance)
+            .count()
     }
-}
 
-/// A set of Turns for a Plane to follow
-pub struct PlanePath {
-    turns: Vec<Turn>,
-}
+    /// Returns the point found at the given distance along the path
+    ///
+    /// Distances outside of the path are clamped to its ends.
+    pub fn point_at(&self, distance: f64) -> (f64, f64) {
+        let distance = distance.clamp(0.0, self.length());
+        let segment = self.segment_at(distance);
+        let (x0, y0) = self.points[segment];
+        let (x1, y1) = self.points[segment + 1];
+        let len = self.distances[segment + 1] - self.distances[segment];
+        if len == 0.0 {
+            return (x0, y0);
+        }
 
-impl PlanePath {
-    /// Construct a new PlanePath
-    pub fn new(turns: Vec<Turn>) -> Self {
-        Self { turns }
+        let t = (distance - self.distances[segment]) / len;
+        (x0 + (x1 - x0) * t, y0 + (y1 - y0) * t)
     }
 
-    pub fn new_main_path(width: f64, height: f64) -> Self {
-        Self::new(vec![
-            Turn::new((width * 0.22, height * 0.26), Direction::Down),
-            Turn::new((width * 0.22, height * 0.72), Direction::Right),
-            Turn::new((width * 0.43, height * 0.72), Direction::Up),
-            Turn::new((width * 0.43, height * 0.25), Direction::Right),
-            Turn::new((width * 0.62, height * 0.25), Direction::Down),
-            Turn::new((width * 0.62, height * 0.72), Direction::Right),
-            Turn::new((width * 0.85, height * 0.72), Direction::Up),
-            Turn::new((width * 0.85, height * 0.26), Direction::Right),
-        ])
+    /// Returns the angle in radians of the segment with the given index
+    pub fn angle_of(&self, segment: usize) -> f64 {
+        let (x0, y0) = self.points[segment];
+        let (x1, y1) = self.points[segment + 1];
+        (y1 - y0).atan2(x1 - x0)
     }
 
-    /// Returns a reference to the Turns
-    pub fn turns(&self) -> &[Turn] {
-        &self.turns
+    /// Returns the shortest distance between the path and the referenced Rect
+    pub fn distance_to_rect(&self, rect: &Rect) -> f64 {
+        let corners = [
+            (rect.x(), rect.y()),
+            (rect.x() + rect.w(), rect.y()),
+            (rect.x() + rect.w(), rect.y() + rect.h()),
+            (rect.x(), rect.y() + rect.h()),
+        ];
+        let edges = [
+            (corners[0], corners[1]),
+            (corners[1], corners[2]),
+            (corners[2], corners[3]),
+            (corners[3], corners[0]),
+        ];
+
+        self.points
+            .windows(2)
+            .map(|pair| {
+                let (a, b) = (pair[0], pair[1]);
+                let inside = |(x, y): (f64, f64)| {
+                    x >= rect.x()
+                        && x <= rect.x() + rect.w()
+                        && y >= rect.y()
+                        && y <= rect.y() + rect.h()
+                };
+                if inside(a) || inside(b) || edges.iter().any(|&(c, d)| crosses(a, b, c, d)) {
+                    return 0.0;
+                }
+                // the closest points of a segment and a Rect that do not touch lie at an
+                // end of the segment or a corner of the Rect
+                let ends = IntoIterator::into_iter([a, b])
+                    .flat_map(|p| edges.iter().map(move |&(c, d)| point_to_segment(p, c, d)));
+                let corners = corners.iter().map(|&c| point_to_segment(c, a, b));
+                ends.chain(corners).fold(f64::INFINITY, f64::min)
+            })
+            .fold(f64::INFINITY, f64::min)
     }
 }
+
+/// Returns the distance from point `p` to the segment from `a` to `b`
+pub(crate) fn point_to_segment(p: (f64, f64), a: (f64, f64), b: (f64, f64)) -> f64 {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let len_sq = dx * dx + dy * dy;
+    let t = if len_sq == 0.0 {
+        0.0
+    } else {
+        (((p.0 - a.0) * dx + (p.1 - a.1) * dy) / len_sq).clamp(0.0, 1.0)
+    };
+    (p.0 - (a.0 + dx * t)).hypot(p.1 - (a.1 + dy * t))
+}
+
+/// Returns true if the segment from `a` to `b` crosses the segment from `c` to `d`
+fn crosses(a: (f64, f64), b: (f64, f64), c: (f64, f64), d: (f64, f64)) -> bool {
+    let side = |p: (f64, f64), q: (f64, f64), r: (f64, f64)| {
+        (q.0 - p.0) * (r.1 - p.1) - (q.1 - p.1) * (r.0 - p.0)
+    };
+    let (d1, d2) = (side(c, d, a), side(c, d, b));
+    let (d3, d4) = (side(a, b, c), side(a, b, d));
+    d1 * d2 <= 0.0 && d3 * d4 <= 0.0 && (d1 != 0.0 || d2 != 0.0 || d3 != 0.0 || d4 != 0.0)
+}
@@ -0,0 +1,11 @@
+use serde::Deserialize;
+
+/// An optional trait of a PlaneKind that changes how Towers and their effects treat it
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PlaneTrait {
+    /// Hidden from Towers without detection
+    Camo,
+    /// Unaffected by effects that list it as immune, such as being soaked
+    Waterproof,
+}
@@ -0,0 +1,32 @@
+use serde::Deserialize;
+
+/// The shots a TowerTier fires when they travel instead of hitting instantly
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProjectileKind {
+    sprite: String,
+    speed: f64,
+    #[serde(default)]
+    homing: bool,
+    radius: f64,
+}
+
+impl ProjectileKind {
+    /// Returns the name of the sprite drawn for the Projectile
+    pub fn sprite(&self) -> &str {
+        &self.sprite
+    }
+    /// Returns the distance travelled in pixels per tick
+    pub fn speed(&self) -> f64 {
+        self.speed
+    }
+    /// Returns true if the Projectile turns to follow its target
+    pub fn homing(&self) -> bool {
+        self.homing
+    }
+    /// Returns the distance within which the Projectile hits a Plane as a multiple of the
+    /// Tower's size
+    pub fn radius(&self) -> f64 {
+        self.radius
+    }
+}
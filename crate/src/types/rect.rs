@@ -2,7 +2,7 @@ use wasm_bindgen::prelude::*;
 
 /// A sub-type of entities used for a logical way to apply construction of new entities
 #[wasm_bindgen]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Rect {
     x: f64,
     y: f64,
@@ -57,4 +57,19 @@ impl Rect {
         self.y = y;
         self.center_y = y + self.h * 0.5;
     }
+
+    /// Returns true if the Rect and the referenced Rect overlap
+    pub fn intersects(&self, other: &Rect) -> bool {
+        self.x < other.x + other.w
+            && other.x < self.x + self.w
+            && self.y < other.y + other.h
+            && other.y < self.y + self.h
+    }
+    /// Returns true if the referenced Rect lies entirely within the Rect
+    pub fn contains(&self, other: &Rect) -> bool {
+        other.x >= self.x
+            && other.y >= self.y
+            && other.x + other.w <= self.x + self.w
+            && other.y + other.h <= self.y + self.h
+    }
 }
@@ -0,0 +1,21 @@
+use serde::Deserialize;
+
+/// The area around a hit where a TowerTier's shots also damage other Planes
+#[derive(Debug, Copy, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Splash {
+    radius: f64,
+    #[serde(default)]
+    falloff: f64,
+}
+
+impl Splash {
+    /// Returns the radius of the area as a multiple of the Tower's size
+    pub fn radius(&self) -> f64 {
+        self.radius
+    }
+    /// Returns the share of the damage lost at the edge of the area
+    pub fn falloff(&self) -> f64 {
+        self.falloff
+    }
+}
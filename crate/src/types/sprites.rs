@@ -0,0 +1,28 @@
+/// The names of every sprite found in `static/`
+pub const SPRITES: &[&str] = &[
+    "Map",
+    "Plane",
+    "Bullet",
+    "BulletRedux",
+    "Glider",
+    "GliderRedux",
+    "WaterBomb",
+    "Blimp",
+    "WaterGunBase",
+    "WaterGunTop",
+    "WaterGunBlast",
+    "SuperSoakerTop",
+    "SuperSoakerBlast",
+    "ExtremeSoakerTop",
+    "ExtremeSoakerBlast",
+    "AcidTowerTop",
+    "AcidTowerBlast",
+    "RadioactiveTowerTop",
+    "RadioactiveTowerBlast",
+    "SodaMakerTop",
+    "SodaMakerBlast",
+    "SparklingWaterTop",
+    "SparklingWaterBlast",
+    "RootBeerTop",
+    "RootBeerBlast",
+];
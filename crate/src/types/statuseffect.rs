@@ -0,0 +1,159 @@
+use serde::Deserialize;
+
+use super::{Clock, DamageType, PlaneTrait};
+
+/// A lasting effect a TowerTier's hits leave on Planes
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EffectKind {
+    name: String,
+    tint: String,
+    duration: u64,
+    #[serde(default)]
+    damage: u32,
+    #[serde(default = "default_interval")]
+    interval: u64,
+    #[serde(default)]
+    shred: u32,
+    #[serde(default)]
+    slow: f64,
+    #[serde(default)]
+    immune: Vec<PlaneTrait>,
+    #[serde(default = "default_stacks")]
+    stacks: u32,
+    #[serde(default)]
+    spread: Option<f64>,
+}
+
+/// The number of ticks between damage if an EffectKind does not set one
+fn default_interval() -> u64 {
+    1
+}
+
+/// The number of times an EffectKind stacks if it does not set one
+fn default_stacks() -> u32 {
+    1
+}
+
+impl EffectKind {
+    /// Returns the name shared by every stack of the effect
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    /// Returns the color affected Planes are tinted with
+    pub fn tint(&self) -> &str {
+        &self.tint
+    }
+    /// Returns the number of ticks the effect lasts after it is last applied
+    pub fn duration(&self) -> u64 {
+        self.duration
+    }
+    /// Returns the damage dealt by each stack every interval
+    pub fn damage(&self) -> u32 {
+        self.damage
+    }
+    /// Returns the number of ticks between damage
+    pub fn interval(&self) -> u64 {
+        self.interval
+    }
+    /// Returns the extra damage each stack makes every hit on the Plane deal
+    pub fn shred(&self) -> u32 {
+        self.shred
+    }
+    /// Returns the share of its speed an affected Plane loses, where 1 freezes it in place
+    pub fn slow(&self) -> f64 {
+        self.slow
+    }
+    /// Returns the traits of Planes the effect does not affect
+    pub fn immune(&self) -> &[PlaneTrait] {
+        &self.immune
+    }
+    /// Returns the most stacks of the effect a Plane can carry
+    pub fn stacks(&self) -> u32 {
+        self.stacks
+    }
+    /// Returns the radius the effect spreads to other Planes within every interval as a
+    /// multiple of the Tower's size, or None if it does not spread
+    pub fn spread(&self) -> Option<f64> {
+        self.spread
+    }
+}
+
+/// An EffectKind carried by a Plane
+#[derive(Debug, Clone)]
+pub struct StatusEffect {
+    kind: EffectKind,
+    damage_type: DamageType,
+    stacks: u32,
+    expires: u64,
+    next_tick: u64,
+    /// The radius in pixels the effect spreads to other Planes within
+    spread: Option<f64>,
+}
+
+impl StatusEffect {
+    /// Construct a new StatusEffect of one stack of the referenced kind dealing damage of the
+    /// given type starting at the Clock's tick
+    pub fn new(
+        kind: &EffectKind,
+        damage_type: DamageType,
+        spread: Option<f64>,
+        clock: &Clock,
+    ) -> Self {
+        Self {
+            kind: kind.clone(),
+            damage_type,
+            stacks: 1,
+            expires: clock.tick() + kind.duration,
+            next_tick: clock.tick() + kind.interval,
+            spread,
+        }
+    }
+
+    /// Returns the kind of the effect
+    pub fn kind(&self) -> &EffectKind {
+        &self.kind
+    }
+    /// Returns the type of the damage the effect deals
+    pub fn damage_type(&self) -> DamageType {
+        self.damage_type
+    }
+    /// Returns the number of stacks of the effect
+    pub fn stacks(&self) -> u32 {
+        self.stacks
+    }
+    /// Returns the radius in pixels the effect spreads within, if it spreads
+    pub fn spread(&self) -> Option<f64> {
+        self.spread
+    }
+    /// Returns the extra damage every hit on the Plane deals
+    pub fn shred(&self) -> u32 {
+        self.kind.shred * self.stacks
+    }
+
+    /// Add a stack of the effect up to its limit, restart its duration, and widen its spread
+    /// to the given radius in pixels if that reaches further
+    pub fn restack(&mut self, spread: Option<f64>, clock: &Clock) {
+        self.stacks = (self.stacks + 1).min(self.kind.stacks);
+        self.expires = clock.tick() + self.kind.duration;
+        self.spread = match (self.spread, spread) {
+            (Some(a), Some(b)) => Some(a.max(b)),
+            (a, b) => a.or(b),
+        };
+    }
+
+    /// Returns the damage the effect deals at the Clock's tick, which is none between
+    /// intervals
+    pub fn tick(&mut self, clock: &Clock) -> u32 {
+        if clock.tick() < self.next_tick {
+            return 0;
+        }
+        self.next_tick += self.kind.interval;
+        self.kind.damage * self.stacks
+    }
+
+    /// Returns true once the effect has worn off
+    pub fn expired(&self, clock: &Clock) -> bool {
+        clock.tick() >= self.expires
+    }
+}
@@ -0,0 +1,39 @@
+/// The rule a Tower uses to choose which Plane in range to fire at
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub enum TargetMode {
+    /// The Plane with the least distance left to the end of its path
+    #[default]
+    First,
+    /// The Plane with the most distance left to the end of its path
+    Last,
+    /// The Plane with the most HP
+    Strongest,
+    /// The Plane with the least HP
+    Weakest,
+    /// The Plane nearest to the Tower
+    Closest,
+}
+
+impl TargetMode {
+    /// Returns the mode that follows this one when cycling through them
+    pub fn next(self) -> Self {
+        match self {
+            TargetMode::First => TargetMode::Last,
+            TargetMode::Last => TargetMode::Strongest,
+            TargetMode::Strongest => TargetMode::Weakest,
+            TargetMode::Weakest => TargetMode::Closest,
+            TargetMode::Closest => TargetMode::First,
+        }
+    }
+
+    /// Returns the name of the mode shown to the player
+    pub fn label(self) -> &'static str {
+        match self {
+            TargetMode::First => "First",
+            TargetMode::Last => "Last",
+            TargetMode::Strongest => "Strongest",
+            TargetMode::Weakest => "Weakest",
+            TargetMode::Closest => "Closest",
+        }
+    }
+}
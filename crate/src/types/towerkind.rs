@@ -0,0 +1,360 @@
+use std::{collections::HashSet, error::Error, fmt, rc::Rc};
+
+use serde::Deserialize;
+
+use super::{DamageType, EffectKind, ProjectileKind, Splash, SPRITES};
+
+/// The stats of one upgrade tier of a TowerFamily
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TowerTier {
+    name: String,
+    top: String,
+    blast: String,
+    cost: i32,
+    range: f64,
+    damage: u32,
+    interval: f64,
+    #[serde(default)]
+    detection: bool,
+    #[serde(default)]
+    projectile: Option<ProjectileKind>,
+    #[serde(default)]
+    splash: Option<Splash>,
+    #[serde(default)]
+    effects: Vec<EffectKind>,
+    #[serde(default, rename = "upgrades")]
+    upgrade_names: Vec<String>,
+    #[serde(skip)]
+    upgrades: Vec<usize>,
+}
+
+impl TowerTier {
+    /// Returns the display name of the tier
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    /// Returns the name of the sprite drawn on top of the Tower's base
+    pub fn top(&self) -> &str {
+        &self.top
+    }
+    /// Returns the name of the sprite drawn when the Tower fires
+    pub fn blast(&self) -> &str {
+        &self.blast
+    }
+    /// Returns the cash needed to build or upgrade into the tier
+    pub fn cost(&self) -> i32 {
+        self.cost
+    }
+    /// Returns the range as a multiple of the Tower's size
+    pub fn range(&self) -> f64 {
+        self.range
+    }
+    /// Returns the damage done with each shot
+    pub fn damage(&self) -> u32 {
+        self.damage
+    }
+    /// Returns the number of ticks between shots
+    pub fn interval(&self) -> f64 {
+        self.interval
+    }
+    /// Returns true if the tier can see camouflaged Planes and reveals them to other Towers
+    pub fn detection(&self) -> bool {
+        self.detection
+    }
+    /// Returns the shots the tier fires, or None if its shots hit instantly
+    pub fn projectile(&self) -> Option<&ProjectileKind> {
+        self.projectile.as_ref()
+    }
+    /// Returns the area the tier's shots damage around a hit, or None if they only damage
+    /// the Plane hit
+    pub fn splash(&self) -> Option<&Splash> {
+        self.splash.as_ref()
+    }
+    /// Returns the lasting effects the tier's shots leave on the Planes they hit
+    pub fn effects(&self) -> &[EffectKind] {
+        &self.effects
+    }
+    /// Returns the indices within the family of the tiers this tier can upgrade into
+    pub fn upgrades(&self) -> &[usize] {
+        &self.upgrades
+    }
+}
+
+/// A kind of Tower and the tree of tiers it upgrades through
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TowerFamily {
+    name: String,
+    base: String,
+    #[serde(default)]
+    damage_type: DamageType,
+    tiers: Vec<TowerTier>,
+}
+
+impl TowerFamily {
+    /// Returns the name of the family
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    /// Returns the name of the sprite drawn beneath every tier
+    pub fn base(&self) -> &str {
+        &self.base
+    }
+    /// Returns the type of damage every tier deals
+    pub fn damage_type(&self) -> DamageType {
+        self.damage_type
+    }
+    /// Returns every tier, starting with the one that is built
+    pub fn tiers(&self) -> &[TowerTier] {
+        &self.tiers
+    }
+    /// Returns the tier with the given index
+    pub fn tier(&self, index: usize) -> Option<&TowerTier> {
+        self.tiers.get(index)
+    }
+    /// Returns the cash needed to build the family's first tier
+    pub fn cost(&self) -> i32 {
+        self.tiers[0].cost
+    }
+
+    /// Returns the names of every sprite drawn for the family's base, tiers, and projectiles
+    fn sprites(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.base.as_str()).chain(self.tiers.iter().flat_map(|tier| {
+            IntoIterator::into_iter([tier.top.as_str(), tier.blast.as_str()])
+                .chain(tier.projectile.iter().map(ProjectileKind::sprite))
+        }))
+    }
+
+    /// Check that every tier has a unique name and playable stats
+    fn validate_tiers(&self) -> Result<(), TowerKindError> {
+        let mut names = HashSet::new();
+        for tier in self.tiers.iter() {
+            if !names.insert(tier.name.as_str()) {
+                return Err(TowerKindError::DuplicateTier {
+                    tower: self.name.clone(),
+                    tier: tier.name.clone(),
+                });
+            }
+            let invalid = |stat| TowerKindError::InvalidStat {
+                tower: self.name.clone(),
+                tier: tier.name.clone(),
+                stat,
+            };
+            let invalid_share = |stat| TowerKindError::InvalidShare {
+                tower: self.name.clone(),
+                tier: tier.name.clone(),
+                stat,
+            };
+            if tier.cost < 0 {
+                return Err(invalid("cost"));
+            }
+            if !tier.range.is_finite() || tier.range <= 0.0 {
+                return Err(invalid("range"));
+            }
+            if !tier.interval.is_finite() || tier.interval <= 0.0 {
+                return Err(invalid("interval"));
+            }
+            if let Some(projectile) = &tier.projectile {
+                if !projectile.speed().is_finite() || projectile.speed() <= 0.0 {
+                    return Err(invalid("projectile speed"));
+                }
+                if !projectile.radius().is_finite() || projectile.radius() <= 0.0 {
+                    return Err(invalid("projectile radius"));
+                }
+            }
+            for effect in tier.effects.iter() {
+                if effect.duration() == 0 {
+                    return Err(invalid("effect duration"));
+                }
+                if effect.interval() == 0 {
+                    return Err(invalid("effect interval"));
+                }
+                if effect.stacks() == 0 {
+                    return Err(invalid("effect stacks"));
+                }
+                if let Some(spread) = effect.spread() {
+                    if !spread.is_finite() || spread <= 0.0 {
+                        return Err(invalid("effect spread"));
+                    }
+                }
+                if !(0.0..=1.0).contains(&effect.slow()) {
+                    return Err(invalid_share("effect slow"));
+                }
+            }
+            if let Some(splash) = &tier.splash {
+                if !splash.radius().is_finite() || splash.radius() <= 0.0 {
+                    return Err(invalid("splash radius"));
+                }
+                if !(0.0..=1.0).contains(&splash.falloff()) {
+                    return Err(invalid_share("splash falloff"));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Resolve the upgrade names of each tier into indices of later tiers
+    ///
+    /// Only allowing upgrades into later tiers keeps the upgrade tree free of cycles.
+    fn resolve_upgrades(&mut self) -> Result<(), TowerKindError> {
+        for i in 0..self.tiers.len() {
+            let mut upgrades = Vec::with_capacity(self.tiers[i].upgrade_names.len());
+            for name in self.tiers[i].upgrade_names.iter() {
+                match self.tiers.iter().position(|tier| &tier.name == name) {
+                    Some(j) if j > i => upgrades.push(j),
+                    _ => {
+                        return Err(TowerKindError::InvalidUpgrade {
+                            tower: self.name.clone(),
+                            tier: self.tiers[i].name.clone(),
+                            upgrade: name.clone(),
+                        })
+                    }
+                }
+            }
+            self.tiers[i].upgrades = upgrades;
+        }
+        Ok(())
+    }
+}
+
+/// An error found while loading a TowerRegistry
+#[derive(Debug)]
+pub enum TowerKindError {
+    /// The document is not valid JSON or does not have the expected shape
+    Parse(serde_json::Error),
+    /// Two families share the same name
+    Duplicate(String),
+    /// Two tiers of a family share the same name
+    DuplicateTier { tower: String, tier: String },
+    /// A tier upgrades into a tier that does not come after it in its family
+    InvalidUpgrade {
+        tower: String,
+        tier: String,
+        upgrade: String,
+    },
+    /// A family does not have any tiers
+    NoTiers(String),
+    /// A family or one of its tiers names a sprite that does not exist
+    UnknownSprite { tower: String, sprite: String },
+    /// A tier has a stat that would make it unplayable
+    InvalidStat {
+        tower: String,
+        tier: String,
+        stat: &'static str,
+    },
+    /// A tier has a stat that is a share of something outside of 0 to 1
+    InvalidShare {
+        tower: String,
+        tier: String,
+        stat: &'static str,
+    },
+}
+
+impl fmt::Display for TowerKindError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TowerKindError::Parse(e) => write!(f, "invalid tower document: {}", e),
+            TowerKindError::Duplicate(name) => {
+                write!(f, "tower family \"{}\" is defined more than once", name)
+            }
+            TowerKindError::DuplicateTier { tower, tier } => write!(
+                f,
+                "tower family \"{}\": tier \"{}\" is defined more than once",
+                tower, tier
+            ),
+            TowerKindError::InvalidUpgrade {
+                tower,
+                tier,
+                upgrade,
+            } => write!(
+                f,
+                "tower family \"{}\", tier \"{}\": \"{}\" is not a later tier of the family",
+                tower, tier, upgrade
+            ),
+            TowerKindError::NoTiers(name) => {
+                write!(f, "tower family \"{}\" does not have any tiers", name)
+            }
+            TowerKindError::UnknownSprite { tower, sprite } => write!(
+                f,
+                "tower family \"{}\": unknown sprite \"{}\"",
+                tower, sprite
+            ),
+            TowerKindError::InvalidStat { tower, tier, stat } => write!(
+                f,
+                "tower family \"{}\", tier \"{}\": {} must be a positive number",
+                tower, tier, stat
+            ),
+            TowerKindError::InvalidShare { tower, tier, stat } => write!(
+                f,
+                "tower family \"{}\", tier \"{}\": {} must be between 0 and 1",
+                tower, tier, stat
+            ),
+        }
+    }
+}
+
+impl Error for TowerKindError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            TowerKindError::Parse(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for TowerKindError {
+    fn from(e: serde_json::Error) -> Self {
+        TowerKindError::Parse(e)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TowerDocument {
+    towers: Vec<TowerFamily>,
+}
+
+/// Every TowerFamily that can be built, in the order they appear in the top bar
+#[derive(Debug, Clone, Default)]
+pub struct TowerRegistry {
+    families: Vec<Rc<TowerFamily>>,
+}
+
+impl TowerRegistry {
+    /// Parse and validate a TowerRegistry from a JSON document
+    pub fn from_json(json: &str) -> Result<Self, TowerKindError> {
+        let mut document: TowerDocument = serde_json::from_str(json)?;
+
+        let mut names = HashSet::new();
+        for family in document.towers.iter_mut() {
+            if !names.insert(family.name.clone()) {
+                return Err(TowerKindError::Duplicate(family.name.clone()));
+            }
+            if family.tiers.is_empty() {
+                return Err(TowerKindError::NoTiers(family.name.clone()));
+            }
+            if let Some(sprite) = family.sprites().find(|sprite| !SPRITES.contains(sprite)) {
+                return Err(TowerKindError::UnknownSprite {
+                    tower: family.name.clone(),
+                    sprite: String::from(sprite),
+                });
+            }
+            family.validate_tiers()?;
+            family.resolve_upgrades()?;
+        }
+
+        Ok(Self {
+            families: document.towers.into_iter().map(Rc::new).collect(),
+        })
+    }
+
+    /// Returns every TowerFamily in order
+    pub fn families(&self) -> &[Rc<TowerFamily>] {
+        &self.families
+    }
+    /// Returns the TowerFamily with the given name
+    pub fn get(&self, name: &str) -> Option<&Rc<TowerFamily>> {
+        self.families.iter().find(|family| family.name == name)
+    }
+}
@@ -1,7 +1,27 @@
 /// A type denoting the status of a Tower
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub enum TowerStatus {
     Normal,
     Selected,
+    /// Selected after the player asked to sell the Tower, waiting for them to confirm
+    ConfirmSell,
+    /// Just placed, when removing the Tower refunds everything until the tick `until`, and
+    /// selected or not
+    Placed {
+        until: u64,
+        selected: bool,
+    },
     Deleted,
 }
+
+impl TowerStatus {
+    /// Returns true if the Tower's selection panel is open
+    pub fn selected(self) -> bool {
+        matches!(
+            self,
+            TowerStatus::Selected
+                | TowerStatus::ConfirmSell
+                | TowerStatus::Placed { selected: true, .. }
+        )
+    }
+}
@@ -0,0 +1,337 @@
+use std::{error::Error, fmt};
+
+use serde::Deserialize;
+
+/// Multipliers applied to every Plane spawned by a Wave
+#[derive(Debug, Copy, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WaveModifiers {
+    hp: f64,
+    speed: f64,
+    bounty: f64,
+}
+
+impl WaveModifiers {
+    /// Returns the multiplier applied to Plane HP
+    pub fn hp(&self) -> f64 {
+        self.hp
+    }
+    /// Returns the multiplier applied to Plane speed
+    pub fn speed(&self) -> f64 {
+        self.speed
+    }
+    /// Returns the multiplier applied to Plane bounty
+    pub fn bounty(&self) -> f64 {
+        self.bounty
+    }
+}
+
+impl Default for WaveModifiers {
+    fn default() -> Self {
+        Self {
+            hp: 1.0,
+            speed: 1.0,
+            bounty: 1.0,
+        }
+    }
+}
+
+/// A number of Planes of one kind spawned at a regular interval
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WaveGroup {
+    plane: String,
+    count: u32,
+    spacing: u64,
+    #[serde(default)]
+    delay: u64,
+    #[serde(default)]
+    paths: Vec<String>,
+}
+
+impl WaveGroup {
+    /// Returns the name of the kind of Plane the group spawns
+    pub fn plane(&self) -> &str {
+        &self.plane
+    }
+    /// Returns the number of Planes in the group
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+    /// Returns the number of ticks between each Plane of the group
+    pub fn spacing(&self) -> u64 {
+        self.spacing
+    }
+    /// Returns the number of ticks after the start of the Wave before the first Plane spawns
+    pub fn delay(&self) -> u64 {
+        self.delay
+    }
+
+    /// Returns the names of the paths the group's Planes take in turn, or none if they take
+    /// every path of the Map in turn
+    pub fn paths(&self) -> &[String] {
+        &self.paths
+    }
+
+    /// Returns the tick offsets from the start of the Wave at which each Plane spawns
+    pub fn spawn_ticks(&self) -> impl Iterator<Item = u64> + '_ {
+        (0..u64::from(self.count)).map(move |i| self.delay + i * self.spacing)
+    }
+}
+
+/// A set of groups of Planes that begin spawning together
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Wave {
+    delay: u64,
+    groups: Vec<WaveGroup>,
+    #[serde(default)]
+    modifiers: WaveModifiers,
+}
+
+impl Wave {
+    /// Returns the number of ticks after the previous Wave started before this Wave starts
+    pub fn delay(&self) -> u64 {
+        self.delay
+    }
+    /// Returns the groups of Planes in the Wave
+    pub fn groups(&self) -> &[WaveGroup] {
+        &self.groups
+    }
+    /// Returns the multipliers applied to each Plane of the Wave
+    pub fn modifiers(&self) -> &WaveModifiers {
+        &self.modifiers
+    }
+}
+
+/// A boss Plane sent after every Wave to end the level
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WaveBoss {
+    plane: String,
+    delay: u64,
+    #[serde(default)]
+    path: Option<String>,
+}
+
+impl WaveBoss {
+    /// Returns the name of the kind of Plane sent as the boss
+    pub fn plane(&self) -> &str {
+        &self.plane
+    }
+    /// Returns the number of ticks after the last Wave started before the boss is sent
+    pub fn delay(&self) -> u64 {
+        self.delay
+    }
+    /// Returns the name of the path the boss takes, or none if it takes the first path of
+    /// the Map
+    pub fn path(&self) -> Option<&str> {
+        self.path.as_deref()
+    }
+}
+
+/// An error found while loading a WaveSchedule
+#[derive(Debug)]
+pub enum WaveError {
+    /// The document is not valid JSON or does not have the expected shape
+    Parse(serde_json::Error),
+    /// The document does not contain any waves
+    NoWaves,
+    /// A wave does not contain any groups
+    NoGroups { wave: usize },
+    /// A group names a kind of Plane that does not exist
+    UnknownPlane {
+        wave: usize,
+        group: usize,
+        plane: String,
+    },
+    /// A group names a path that the Map does not have
+    UnknownPath {
+        wave: usize,
+        group: usize,
+        path: String,
+    },
+    /// A group spawns no Planes
+    EmptyGroup { wave: usize, group: usize },
+    /// A modifier is not a positive finite number
+    InvalidModifier { wave: usize, modifier: &'static str },
+    /// The boss is a kind of Plane that does not exist
+    UnknownBoss(String),
+    /// The boss takes a path that the Map does not have
+    UnknownBossPath(String),
+}
+
+impl fmt::Display for WaveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WaveError::Parse(e) => write!(f, "invalid wave document: {}", e),
+            WaveError::NoWaves => write!(f, "the wave document does not contain any waves"),
+            WaveError::NoGroups { wave } => write!(f, "wave {} does not contain any groups", wave),
+            WaveError::UnknownPlane { wave, group, plane } => write!(
+                f,
+                "wave {}, group {}: unknown plane kind \"{}\"",
+                wave, group, plane
+            ),
+            WaveError::UnknownPath { wave, group, path } => write!(
+                f,
+                "wave {}, group {}: unknown path \"{}\"",
+                wave, group, path
+            ),
+            WaveError::EmptyGroup { wave, group } => {
+                write!(
+                    f,
+                    "wave {}, group {}: count must be at least 1",
+                    wave, group
+                )
+            }
+            WaveError::InvalidModifier { wave, modifier } => write!(
+                f,
+                "wave {}: the {} modifier must be a positive number",
+                wave, modifier
+            ),
+            WaveError::UnknownBoss(plane) => {
+                write!(f, "the boss: unknown plane kind \"{}\"", plane)
+            }
+            WaveError::UnknownBossPath(path) => write!(f, "the boss: unknown path \"{}\"", path),
+        }
+    }
+}
+
+impl Error for WaveError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            WaveError::Parse(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for WaveError {
+    fn from(e: serde_json::Error) -> Self {
+        WaveError::Parse(e)
+    }
+}
+
+/// The ordered Waves of Planes that attack during a game
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WaveSchedule {
+    waves: Vec<Wave>,
+    #[serde(default)]
+    boss: Option<WaveBoss>,
+}
+
+impl WaveSchedule {
+    /// Parse and validate a WaveSchedule from a JSON document
+    ///
+    /// `is_plane` reports whether a plane kind named by a group or the boss exists, and
+    /// `is_path` whether a path named by a group or the boss exists.
+    /// Waves and groups in errors are numbered from 1.
+    pub fn from_json(
+        json: &str,
+        is_plane: impl Fn(&str) -> bool,
+        is_path: impl Fn(&str) -> bool,
+    ) -> Result<Self, WaveError> {
+        let schedule: Self = serde_json::from_str(json)?;
+        schedule.validate()?;
+        schedule.validate_planes(is_plane)?;
+        schedule.validate_paths(is_path)?;
+        Ok(schedule)
+    }
+
+    fn validate(&self) -> Result<(), WaveError> {
+        if self.waves.is_empty() {
+            return Err(WaveError::NoWaves);
+        }
+        for (w, wave) in self.waves.iter().enumerate() {
+            let wave_num = w + 1;
+            if wave.groups.is_empty() {
+                return Err(WaveError::NoGroups { wave: wave_num });
+            }
+            for (g, group) in wave.groups.iter().enumerate() {
+                if group.count == 0 {
+                    return Err(WaveError::EmptyGroup {
+                        wave: wave_num,
+                        group: g + 1,
+                    });
+                }
+            }
+            let modifiers = &wave.modifiers;
+            for (modifier, value) in [
+                ("hp", modifiers.hp),
+                ("speed", modifiers.speed),
+                ("bounty", modifiers.bounty),
+            ] {
+                if !value.is_finite() || value <= 0.0 {
+                    return Err(WaveError::InvalidModifier {
+                        wave: wave_num,
+                        modifier,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that every plane named by a group or the boss exists, as reported by `is_plane`
+    ///
+    /// Waves and groups in errors are numbered from 1.
+    pub fn validate_planes(&self, is_plane: impl Fn(&str) -> bool) -> Result<(), WaveError> {
+        for (w, wave) in self.waves.iter().enumerate() {
+            for (g, group) in wave.groups.iter().enumerate() {
+                if !is_plane(&group.plane) {
+                    return Err(WaveError::UnknownPlane {
+                        wave: w + 1,
+                        group: g + 1,
+                        plane: group.plane.clone(),
+                    });
+                }
+            }
+        }
+        if let Some(boss) = &self.boss {
+            if !is_plane(&boss.plane) {
+                return Err(WaveError::UnknownBoss(boss.plane.clone()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that every path named by a group or the boss exists, as reported by `is_path`
+    ///
+    /// Waves and groups in errors are numbered from 1.
+    pub fn validate_paths(&self, is_path: impl Fn(&str) -> bool) -> Result<(), WaveError> {
+        for (w, wave) in self.waves.iter().enumerate() {
+            for (g, group) in wave.groups.iter().enumerate() {
+                if let Some(path) = group.paths.iter().find(|path| !is_path(path)) {
+                    return Err(WaveError::UnknownPath {
+                        wave: w + 1,
+                        group: g + 1,
+                        path: path.clone(),
+                    });
+                }
+            }
+        }
+        if let Some(path) = self
+            .boss
+            .as_ref()
+            .and_then(|boss| boss.path.as_ref())
+            .filter(|path| !is_path(path))
+        {
+            return Err(WaveError::UnknownBossPath(path.clone()));
+        }
+        Ok(())
+    }
+
+    /// Returns the Waves in the order they are sent
+    pub fn waves(&self) -> &[Wave] {
+        &self.waves
+    }
+    /// Returns the boss sent after every Wave, if any
+    pub fn boss(&self) -> Option<&WaveBoss> {
+        self.boss.as_ref()
+    }
+    /// Returns the number of rounds in the schedule, with the boss as a round of its own
+    pub fn rounds(&self) -> usize {
+        self.waves.len() + usize::from(self.boss.is_some())
+    }
+}
@@ -0,0 +1,216 @@
+//! Tests for loading Maps, playing Simulations on them, and sending Planes down their paths.
+
+use std::rc::Rc;
+
+use waterguns_vs_paperplanes_wasm::{
+    render::{DrawCommand, RecordingRenderer},
+    simulation::{Input, Simulation},
+    types::{Map, MapError, PlacementError, PlaneRegistry, TowerRegistry, WaveSchedule},
+};
+
+const LEVEL: &str = r#"{
+    "name": "Alley",
+    "background": "Map",
+    "paths": [
+        { "name": "north", "spawn": [0.5, -0.25], "points": [[0.5, 0.5]], "exit": [1.25, 0.5] },
+        { "name": "south", "spawn": [0.5, 1.1], "exit": [0.5, 0.6] }
+    ],
+    "blocked": [ { "x": 0.4, "y": 0.4, "w": 0.2, "h": 0.2 } ]
+}"#;
+
+#[test]
+fn map_paths_scale_to_the_simulation() {
+    let map = Map::from_json(LEVEL).unwrap();
+    assert_eq!(map.name(), "Alley");
+    assert!(map.path("south").is_some());
+    assert!(map.buildable().is_empty());
+    assert_eq!(map.blocked()[0].to_rect(200.0, 100.0).w(), 40.0);
+
+    let sim = Simulation::with_map(200.0, 100.0, map);
+    assert_eq!(sim.paths().len(), 2);
+    assert_eq!(
+        sim.path().points(),
+        [(100.0, -25.0), (100.0, 50.0), (250.0, 50.0)]
+    );
+    assert_eq!(sim.path().length(), 225.0);
+}
+
+#[test]
+fn planes_spawn_on_the_map_and_it_is_drawn() {
+    let map = Map::from_json(LEVEL).unwrap();
+    let mut sim = Simulation::with_map(1000.0, 500.0, map);
+    while sim.planes().is_empty() {
+        sim.step(&Input::new(0.0, 0.0, false, false));
+    }
+    let plane = &sim.planes()[0];
+    assert_eq!(plane.center_x(), 500.0);
+    assert!(plane.center_y() > -125.0 && plane.center_y() < 0.0);
+
+    let mut renderer = RecordingRenderer::new();
+    sim.draw(&mut renderer).unwrap();
+    assert!(matches!(
+        &renderer.commands()[0],
+        DrawCommand::Sprite { name, .. } if name == "Map"
+    ));
+}
+
+#[test]
+fn invalid_maps_are_rejected() {
+    let err =
+        Map::from_json(r#"{ "name": "Empty", "background": "Map", "paths": [] }"#).unwrap_err();
+    assert!(matches!(err, MapError::NoPaths));
+
+    let err = Map::from_json(
+        r#"{ "name": "Typo", "background": "Mpa",
+             "paths": [ { "name": "main", "spawn": [0, 0], "exit": [1, 1] } ] }"#,
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), "unknown background sprite \"Mpa\"");
+
+    let err = Map::from_json(
+        r#"{ "name": "Flat", "background": "Map",
+             "paths": [ { "name": "main", "spawn": [0, 0], "exit": [1, 1] } ],
+             "buildable": [ { "x": 0, "y": 0, "w": 1, "h": 0 } ] }"#,
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "buildable region 1: width and height must be positive numbers"
+    );
+
+    let err = Map::from_json(
+        r#"{ "name": "Fine", "background": "Map",
+             "paths": [ { "name": "main", "spawn": [0, 0], "exit": [1, 1] } ],
+             "grid": { "cell": 0.000001 } }"#,
+    )
+    .unwrap_err();
+    assert!(matches!(err, MapError::InvalidGrid));
+}
+
+#[test]
+fn groups_take_their_paths_in_turn() {
+    let map = Map::from_json(
+        r#"{ "name": "Fork", "background": "Map", "paths": [
+            { "name": "upper", "spawn": [0, 0.5], "points": [[0.25, 0.5], [0.5, 0.25], [0.75, 0.5]], "exit": [1, 0.5] },
+            { "name": "lower", "spawn": [0, 0.5], "points": [[0.25, 0.5], [0.5, 0.75], [0.75, 0.5]], "exit": [1, 0.5] },
+            { "name": "side", "spawn": [0.5, 1], "exit": [0.5, 0.5] }
+        ] }"#,
+    )
+    .unwrap();
+    let kinds = PlaneRegistry::from_json(include_str!("../data/planes.json")).unwrap();
+    let towers = TowerRegistry::from_json(include_str!("../data/towers.json")).unwrap();
+    let schedule = WaveSchedule::from_json(
+        r#"{ "waves": [ { "delay": 0, "groups": [
+            { "plane": "Basic", "count": 4, "spacing": 1, "paths": ["upper", "lower"] },
+            { "plane": "Blimp", "count": 3, "spacing": 1, "delay": 10 }
+        ] } ] }"#,
+        |name| kinds.contains(name),
+        |name| map.path(name).is_some(),
+    )
+    .unwrap();
+
+    let mut sim = Simulation::with_data(400.0, 200.0, map, kinds, towers, schedule).unwrap();
+    for _ in 0..13 {
+        sim.step(&Input::new(0.0, 0.0, false, false));
+    }
+
+    let path_of = |i: usize| {
+        sim.paths()
+            .iter()
+            .position(|path| Rc::ptr_eq(path, sim.planes()[i].path()))
+    };
+    let paths: Vec<_> = (0..sim.planes().len()).map(path_of).collect();
+    // the Basic Planes alternate between the named fork, the Blimps take every path
+    assert_eq!(
+        paths,
+        [
+            Some(0),
+            Some(1),
+            Some(0),
+            Some(1),
+            Some(0),
+            Some(1),
+            Some(2)
+        ]
+    );
+}
+
+#[test]
+fn groups_must_name_paths_of_the_map() {
+    let map = Map::from_json(LEVEL).unwrap();
+    let err = WaveSchedule::from_json(
+        r#"{ "waves": [ { "delay": 0, "groups": [
+            { "plane": "Basic", "count": 1, "spacing": 0, "paths": ["north", "west"] }
+        ] } ] }"#,
+        |_| true,
+        |name| map.path(name).is_some(),
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), "wave 1, group 1: unknown path \"west\"");
+
+    // a schedule checked against one Map cannot be played on another
+    let schedule = WaveSchedule::from_json(
+        r#"{ "waves": [ { "delay": 0, "groups": [
+            { "plane": "Basic", "count": 1, "spacing": 0, "paths": ["north"] }
+        ] } ] }"#,
+        |_| true,
+        |name| map.path(name).is_some(),
+    )
+    .unwrap();
+    let backyard = Map::from_json(include_str!("../data/maps/backyard.json")).unwrap();
+    let kinds = PlaneRegistry::from_json(include_str!("../data/planes.json")).unwrap();
+    let towers = TowerRegistry::from_json(include_str!("../data/towers.json")).unwrap();
+    let err = Simulation::with_data(1000.0, 500.0, backyard, kinds, towers, schedule)
+        .err()
+        .unwrap();
+    assert_eq!(err.to_string(), "wave 1, group 1: unknown path \"north\"");
+}
+
+#[test]
+fn towers_snap_to_free_grid_cells() {
+    let map = Map::from_json(
+        r#"{ "name": "Grid", "background": "Map",
+             "paths": [ { "name": "main", "spawn": [0, 0.9], "exit": [1, 0.9] } ],
+             "grid": { "cell": 0.1, "offset": [0.0, 0.05] } }"#,
+    )
+    .unwrap();
+    let mut sim = Simulation::with_map(1000.0, 500.0, map);
+    let grid = *sim.grid().unwrap();
+    assert_eq!(grid.cell(), 50.0);
+    let button = sim.buttons()[0].rect().clone();
+
+    // a click anywhere in a cell builds in its center
+    sim.step(&Input::new(
+        button.center_x(),
+        button.center_y(),
+        false,
+        true,
+    ));
+    sim.step(&Input::new(310.0, 240.0, false, true));
+    assert_eq!(sim.towers().len(), 1);
+    assert_eq!(
+        (sim.towers()[0].center_x(), sim.towers()[0].center_y()),
+        (325.0, 250.0)
+    );
+    assert!(sim.is_occupied((6, 4)));
+
+    // the cell is taken until the Tower is gone
+    sim.step(&Input::new(
+        button.center_x(),
+        button.center_y(),
+        false,
+        true,
+    ));
+    sim.step(&Input::new(340.0, 270.0, false, true));
+    assert_eq!(sim.towers().len(), 1);
+    assert_eq!(sim.feedback(), Some(PlacementError::Occupied));
+
+    sim.set_grid_overlay(true);
+    let mut renderer = RecordingRenderer::new();
+    sim.draw(&mut renderer).unwrap();
+    let occupied = grid.cell_rect((6, 4));
+    assert!(renderer.commands().contains(&DrawCommand::FillRect {
+        rect: occupied,
+        color: String::from("#ff0000"),
+    }));
+}
@@ -0,0 +1,541 @@
+//! Tests for loading PlaneKinds, spawning Planes from them, and tracking their flight and
+//! status effects.
+
+use std::rc::Rc;
+
+use waterguns_vs_paperplanes_wasm::{
+    entity::PaperPlane,
+    simulation::{Input, Simulation},
+    types::{
+        Clock, DamageType, Hit, Map, PlaneKindError, PlanePath, PlaneRegistry, PlaneTrait,
+        TowerRegistry, WaveSchedule,
+    },
+};
+
+#[test]
+fn planes_take_their_stats_from_their_kind() {
+    let kinds = PlaneRegistry::from_json(
+        r#"{ "planes": [
+            { "name": "Kite", "sprite": "Glider", "speed": 2.5, "hp": 40,
+              "damage": 4, "bounty": 7, "traits": ["waterproof"] }
+        ] }"#,
+    )
+    .unwrap();
+    let kind = kinds.get("Kite").unwrap();
+    let path = Rc::new(PlanePath::new(vec![(0.0, 0.0), (100.0, 0.0)]));
+    let plane = PaperPlane::spawn(0, kind, &path, 10.0);
+
+    assert!(Rc::ptr_eq(kind, kinds.get("Kite").unwrap()));
+    assert!(Rc::ptr_eq(plane.path(), &path));
+    assert_eq!((plane.x(), plane.y(), plane.w()), (-5.0, -5.0, 10.0));
+    assert_eq!(plane.kind().sprite(), "Glider");
+    assert!(plane.kind().has_trait(PlaneTrait::Waterproof));
+    assert!(!plane.kind().has_trait(PlaneTrait::Camo));
+    assert_eq!(plane.hp().curr_hp(), 40);
+    assert_eq!(plane.damage(), 4);
+    assert_eq!(plane.bounty(), 7);
+}
+
+#[test]
+fn planes_track_their_progress_along_the_path() {
+    let kinds = PlaneRegistry::from_json(include_str!("../data/planes.json")).unwrap();
+    let map = Map::from_json(include_str!("../data/maps/backyard.json")).unwrap();
+    let path = Rc::new(map.paths()[0].to_plane_path(1366.0, 768.0));
+    let mut plane = PaperPlane::spawn(0, kinds.get("Bullet").unwrap(), &path, 10.0);
+    assert_eq!(plane.progress().distance(), 0.0);
+    assert_eq!(plane.progress().segment(), 0);
+
+    let mut segments = vec![0];
+    let mut ticks = 0;
+    while !plane.finished() {
+        plane.fly();
+        ticks += 1;
+        if plane.progress().segment() != *segments.last().unwrap() {
+            segments.push(plane.progress().segment());
+        }
+    }
+
+    assert_eq!(segments, (0..path.segments()).collect::<Vec<_>>());
+    assert!((plane.progress().distance() - ticks as f64 * 3.0).abs() < 1e-6);
+}
+
+#[test]
+fn fast_planes_stay_on_the_path() {
+    let kinds = PlaneRegistry::from_json(
+        r#"{ "planes": [
+            { "name": "Rocket", "sprite": "Bullet", "speed": 37.0, "hp": 1, "damage": 1, "bounty": 1 }
+        ] }"#,
+    )
+    .unwrap();
+    let path = Rc::new(PlanePath::new(vec![
+        (0.0, 0.0),
+        (100.0, 0.0),
+        (160.0, 80.0),
+        (160.0, 300.0),
+    ]));
+    let mut plane = PaperPlane::spawn(0, kinds.get("Rocket").unwrap(), &path, 10.0);
+
+    while !plane.finished() {
+        plane.fly();
+        let (x, y) = (plane.center_x(), plane.center_y());
+        let on_path = path.points().windows(2).any(|pair| {
+            let ((x0, y0), (x1, y1)) = (pair[0], pair[1]);
+            let cross = (x1 - x0) * (y - y0) - (y1 - y0) * (x - x0);
+            let within = (x - x0) * (x - x1) <= 1e-9 && (y - y0) * (y - y1) <= 1e-9;
+            cross.abs() < 1e-6 && within
+        });
+        assert!(on_path, "({}, {}) is off the path", x, y);
+    }
+    assert_eq!((plane.center_x(), plane.center_y()), (160.0, 300.0));
+}
+
+#[test]
+fn effects_stack_tick_and_wear_off() {
+    let towers = TowerRegistry::from_json(
+        r##"{ "towers": [ { "name": "Vat", "base": "WaterGunBase", "tiers": [
+            { "name": "Vat", "top": "AcidTowerTop", "blast": "AcidTowerBlast",
+              "cost": 10, "range": 2.0, "damage": 1, "interval": 5, "effects": [
+                { "name": "acid", "tint": "#66ff00", "duration": 30,
+                  "damage": 2, "interval": 10, "stacks": 2, "spread": 1.5 },
+                { "name": "corrosion", "tint": "#ff9900", "duration": 60, "shred": 3 }
+            ] }
+        ] } ] }"##,
+    )
+    .unwrap();
+    let kinds = PlaneRegistry::from_json(
+        r#"{ "planes": [
+            { "name": "Crate", "sprite": "Blimp", "speed": 1.0, "hp": 200, "damage": 1, "bounty": 1 }
+        ] }"#,
+    )
+    .unwrap();
+    let path = Rc::new(PlanePath::new(vec![(0.0, 0.0), (100.0, 0.0)]));
+    let mut plane = PaperPlane::spawn(0, kinds.get("Crate").unwrap(), &path, 10.0);
+    let hit = Hit::new(
+        1,
+        DamageType::Acid,
+        towers.families()[0].tiers()[0].effects(),
+        10.0,
+        false,
+    );
+    let mut clock = Clock::new();
+
+    // the second hit is shredded by the corrosion the first left
+    plane.take_hit(&hit, &clock);
+    plane.take_hit(&hit, &clock);
+    assert_eq!(plane.hp().curr_hp(), 200 - 1 - 4);
+
+    // acid stacks up to its limit
+    plane.take_hit(&hit, &clock);
+    assert_eq!(plane.hp().curr_hp(), 195 - 4);
+    let stacks: Vec<_> = plane.effects().iter().map(|e| e.stacks()).collect();
+    assert_eq!(stacks, [2, 1]);
+
+    // acid deals the damage of every stack once per interval and spreads as it does
+    let mut spreads = Vec::new();
+    for _ in 0..30 {
+        clock.advance();
+        for effect in plane.update_effects(&clock) {
+            spreads.push((String::from(effect.kind().name()), effect.spread()));
+        }
+    }
+    assert_eq!(plane.hp().curr_hp(), 191 - 3 * 4);
+    assert_eq!(spreads, vec![(String::from("acid"), Some(15.0)); 3]);
+
+    // only the corrosion outlasts the acid
+    assert!(!plane.has_effect("acid"));
+    assert!(plane.has_effect("corrosion"));
+    // a Plane that caught the acid from a neighbour spreads it once it is hit directly
+    let mut neighbour = PaperPlane::spawn(1, kinds.get("Crate").unwrap(), &path, 10.0);
+    let acid = &towers.families()[0].tiers()[0].effects()[0];
+    neighbour.afflict(acid, DamageType::Acid, None, &clock);
+    assert_eq!(neighbour.effects()[0].spread(), None);
+    neighbour.take_hit(&hit, &clock);
+    assert_eq!(neighbour.effects()[0].spread(), Some(15.0));
+    neighbour.afflict(acid, DamageType::Acid, None, &clock);
+    assert_eq!(neighbour.effects()[0].spread(), Some(15.0));
+}
+
+#[test]
+fn slows_stack_and_spare_immune_planes() {
+    let towers = TowerRegistry::from_json(
+        r##"{ "towers": [ { "name": "Hose", "base": "WaterGunBase", "tiers": [
+            { "name": "Hose", "top": "WaterGunTop", "blast": "WaterGunBlast",
+              "cost": 10, "range": 2.0, "damage": 0, "interval": 5, "effects": [
+                { "name": "soaked", "tint": "#3399ff", "duration": 30, "slow": 0.5,
+                  "immune": ["waterproof"] }
+            ] },
+            { "name": "Ice", "top": "WaterGunTop", "blast": "WaterGunBlast",
+              "cost": 10, "range": 2.0, "damage": 0, "interval": 5, "effects": [
+                { "name": "frozen", "tint": "#aaddff", "duration": 10, "slow": 1.0 }
+            ] }
+        ] } ] }"##,
+    )
+    .unwrap();
+    let kinds = PlaneRegistry::from_json(
+        r#"{ "planes": [
+            { "name": "Kite", "sprite": "Plane", "speed": 2.0, "hp": 10, "damage": 1, "bounty": 1 },
+            { "name": "Balloon", "sprite": "WaterBomb", "speed": 2.0, "hp": 10, "damage": 1,
+              "bounty": 1, "traits": ["waterproof"] }
+        ] }"#,
+    )
+    .unwrap();
+    let tiers = towers.families()[0].tiers();
+    let (soak, freeze) = (
+        Hit::new(0, DamageType::Water, tiers[0].effects(), 10.0, false),
+        Hit::new(0, DamageType::Water, tiers[1].effects(), 10.0, false),
+    );
+    let path = Rc::new(PlanePath::new(vec![(0.0, 0.0), (1000.0, 0.0)]));
+    let mut kite = PaperPlane::spawn(0, kinds.get("Kite").unwrap(), &path, 10.0);
+    let mut balloon = PaperPlane::spawn(1, kinds.get("Balloon").unwrap(), &path, 10.0);
+    let mut clock = Clock::new();
+
+    kite.take_hit(&soak, &clock);
+    balloon.take_hit(&soak, &clock);
+    assert!(!balloon.has_effect("soaked"));
+    for _ in 0..5 {
+        kite.fly();
+        balloon.fly();
+    }
+    assert_eq!(kite.progress().distance(), 5.0);
+    assert_eq!(balloon.progress().distance(), 10.0);
+
+    // a freeze holds the Plane in place until it wears off
+    kite.take_hit(&freeze, &clock);
+    assert_eq!(kite.speed_factor(), 0.0);
+    kite.fly();
+    assert_eq!(kite.progress().distance(), 5.0);
+
+    for _ in 0..30 {
+        clock.advance();
+        kite.update_effects(&clock);
+    }
+    assert!(kite.effects().is_empty());
+    kite.fly();
+    assert_eq!(kite.progress().distance(), 7.0);
+}
+
+#[test]
+fn damage_is_resolved_against_resistances_and_armor() {
+    let kinds = PlaneRegistry::from_json(include_str!("../data/planes.json")).unwrap();
+    let path = Rc::new(PlanePath::new(vec![(0.0, 0.0), (100.0, 0.0)]));
+    let plane = |name| PaperPlane::spawn(0, kinds.get(name).unwrap(), &path, 10.0);
+
+    assert_eq!(
+        plane("Basic").resolve_damage(10, DamageType::Water, true),
+        10
+    );
+    assert_eq!(
+        plane("WaterBomb").resolve_damage(10, DamageType::Water, true),
+        0
+    );
+    assert_eq!(
+        plane("WaterBomb").resolve_damage(10, DamageType::Acid, true),
+        10
+    );
+    assert_eq!(
+        plane("Glider").resolve_damage(10, DamageType::Fizz, true),
+        15
+    );
+
+    // armor stops part of every hit but not damage over time
+    let blimp = plane("Blimp");
+    assert_eq!(blimp.resolve_damage(10, DamageType::Water, true), 8);
+    assert_eq!(blimp.resolve_damage(10, DamageType::Acid, true), 6);
+    assert_eq!(blimp.resolve_damage(10, DamageType::Acid, false), 8);
+    assert_eq!(blimp.resolve_damage(1, DamageType::Water, true), 0);
+}
+
+#[test]
+fn destroyed_planes_spawn_their_children_where_they_fell() {
+    let map = Map::from_json(
+        r#"{ "name": "Lane", "background": "Map",
+             "paths": [ { "name": "main", "spawn": [0, 0.5], "exit": [1, 0.5] } ] }"#,
+    )
+    .unwrap();
+    let kinds = PlaneRegistry::from_json(
+        r#"{ "planes": [
+            { "name": "Pinata", "sprite": "Blimp", "speed": 1.0, "hp": 5, "damage": 1, "bounty": 4,
+              "children": [ { "plane": "Candy", "count": 2 }, { "plane": "Confetti", "count": 1 } ] },
+            { "name": "Candy", "sprite": "Plane", "speed": 1.0, "hp": 1, "damage": 1, "bounty": 1 },
+            { "name": "Confetti", "sprite": "Plane", "speed": 1.0, "hp": 1, "damage": 1, "bounty": 1 }
+        ] }"#,
+    )
+    .unwrap();
+    let towers = TowerRegistry::from_json(
+        r#"{ "towers": [ { "name": "Bat", "base": "WaterGunBase", "tiers": [
+            { "name": "Bat", "top": "WaterGunTop", "blast": "WaterGunBlast",
+              "cost": 10, "range": 3.0, "damage": 100, "interval": 1000 }
+        ] } ] }"#,
+    )
+    .unwrap();
+    let schedule = WaveSchedule::from_json(
+        r#"{ "waves": [ { "delay": 0, "groups": [
+            { "plane": "Pinata", "count": 1, "spacing": 0 }
+        ], "modifiers": { "hp": 2.0 } } ] }"#,
+        |name| kinds.contains(name),
+        |name| name == "main",
+    )
+    .unwrap();
+    let mut sim = Simulation::with_data(1000.0, 500.0, map, kinds, towers, schedule).unwrap();
+
+    for _ in 0..100 {
+        sim.step(&Input::new(0.0, 0.0, false, false));
+    }
+    let button = sim.buttons()[0].rect().clone();
+    sim.step(&Input::new(
+        button.center_x(),
+        button.center_y(),
+        false,
+        true,
+    ));
+    let fell = sim.planes()[0].progress().distance() + 1.0;
+    sim.step(&Input::new(150.0, 300.0, false, true));
+
+    // the children line up behind each other from where the Pinata fell
+    let children: Vec<_> = sim
+        .planes()
+        .iter()
+        .map(|plane| (plane.id(), plane.kind().name(), plane.progress().distance()))
+        .collect();
+    assert_eq!(
+        children,
+        [
+            (1, "Candy", fell),
+            (2, "Candy", fell - 12.5),
+            (3, "Confetti", fell - 25.0)
+        ]
+    );
+    assert_eq!(sim.planes()[0].center_x(), fell);
+    // the children are as tough as the wave made the Pinata
+    assert!(sim.planes().iter().all(|plane| plane.hp().max_hp() == 2));
+    assert_eq!(sim.cash(), 4);
+}
+
+#[test]
+fn children_of_planes_destroyed_at_the_exit_stay_on_the_path() {
+    let map = Map::from_json(
+        r#"{ "name": "Lane", "background": "Map",
+             "paths": [ { "name": "main", "spawn": [0, 0.5], "exit": [1, 0.5] } ] }"#,
+    )
+    .unwrap();
+    let kinds = PlaneRegistry::from_json(
+        r#"{ "planes": [
+            { "name": "Pinata", "sprite": "Blimp", "speed": 1.0, "hp": 5, "damage": 1, "bounty": 4,
+              "children": [ { "plane": "Candy", "count": 2 } ] },
+            { "name": "Candy", "sprite": "Plane", "speed": 1.0, "hp": 1, "damage": 1, "bounty": 1 }
+        ] }"#,
+    )
+    .unwrap();
+    let towers = TowerRegistry::from_json(
+        r#"{ "towers": [ { "name": "Bat", "base": "WaterGunBase", "tiers": [
+            { "name": "Bat", "top": "WaterGunTop", "blast": "WaterGunBlast",
+              "cost": 10, "range": 5.0, "damage": 100, "interval": 1000 }
+        ] } ] }"#,
+    )
+    .unwrap();
+    let schedule = WaveSchedule::from_json(
+        r#"{ "waves": [ { "delay": 0, "groups": [
+            { "plane": "Pinata", "count": 1, "spacing": 0 }
+        ] } ] }"#,
+        |name| kinds.contains(name),
+        |name| name == "main",
+    )
+    .unwrap();
+    let mut sim = Simulation::with_data(1000.0, 500.0, map, kinds, towers, schedule).unwrap();
+
+    // the Pinata is shot down on the tick it flies past the exit
+    while sim.planes().is_empty() || sim.planes()[0].remaining() > 2.0 {
+        sim.step(&Input::new(0.0, 0.0, false, false));
+    }
+    let button = sim.buttons()[0].rect().clone();
+    sim.step(&Input::new(
+        button.center_x(),
+        button.center_y(),
+        false,
+        true,
+    ));
+    sim.step(&Input::new(900.0, 300.0, false, true));
+    assert_eq!(sim.cash(), 4);
+
+    let distances: Vec<_> = sim
+        .planes()
+        .iter()
+        .map(|plane| plane.progress().distance())
+        .collect();
+    assert_eq!(distances, [987.5, 975.0]);
+    sim.step(&Input::new(0.0, 0.0, false, false));
+    assert_eq!(sim.planes().len(), 2);
+    assert_eq!(sim.hp().curr_hp(), sim.hp().max_hp());
+}
+
+#[test]
+fn bosses_change_speed_raise_shields_and_call_escorts_in_phases() {
+    let kinds = PlaneRegistry::from_json(
+        r#"{ "planes": [
+            { "name": "Titan", "sprite": "Blimp", "speed": 2.0, "hp": 100, "damage": 1, "bounty": 1,
+              "boss": true, "phases": [
+                  { "hp": 0.5, "speed": 0.5, "shield": 10,
+                    "escorts": [ { "plane": "Kite", "count": 2 } ] },
+                  { "hp": 0.2, "speed": 2.0 }
+              ] },
+            { "name": "Kite", "sprite": "Plane", "speed": 1.0, "hp": 1, "damage": 1, "bounty": 1 }
+        ] }"#,
+    )
+    .unwrap();
+    let path = Rc::new(PlanePath::new(vec![(0.0, 0.0), (1000.0, 0.0)]));
+    let mut plane = PaperPlane::spawn(0, kinds.get("Titan").unwrap(), &path, 10.0);
+    let mut clock = Clock::new();
+    let hit = |damage| Hit::new(damage, DamageType::Water, &[], 10.0, false);
+
+    plane.take_hit(&hit(40), &clock);
+    assert!(plane.begin_phases(&clock).is_empty());
+    assert_eq!(plane.phase(), 0);
+
+    // falling to half HP halves its speed, raises its shield, and calls two escorts
+    plane.take_hit(&hit(10), &clock);
+    let escorts = plane.begin_phases(&clock);
+    assert_eq!(escorts.len(), 1);
+    assert_eq!((escorts[0].plane(), escorts[0].count()), ("Kite", 2));
+    assert_eq!(plane.phase(), 1);
+    plane.fly();
+    assert_eq!(plane.progress().distance(), 1.0);
+
+    plane.take_hit(&hit(45), &clock);
+    assert!(plane.shielded(&clock));
+    assert_eq!(plane.hp().curr_hp(), 50);
+
+    for _ in 0..10 {
+        clock.advance();
+    }
+    assert!(!plane.shielded(&clock));
+    plane.take_hit(&hit(45), &clock);
+    assert!(plane.begin_phases(&clock).is_empty());
+    assert_eq!(plane.phase(), 2);
+    plane.fly();
+    assert_eq!(plane.progress().distance(), 5.0);
+}
+
+#[test]
+fn default_planes_load() {
+    let kinds = PlaneRegistry::from_json(include_str!("../data/planes.json")).unwrap();
+    for name in [
+        "Basic",
+        "Bullet",
+        "BulletRedux",
+        "Glider",
+        "GliderRedux",
+        "WaterBomb",
+        "Blimp",
+        "Mothership",
+    ] {
+        assert!(kinds.contains(name), "missing {}", name);
+    }
+    assert!(kinds.get("Mothership").unwrap().boss());
+}
+
+#[test]
+fn invalid_kinds_are_rejected() {
+    let duplicate = PlaneRegistry::from_json(
+        r#"{ "planes": [
+            { "name": "Basic", "sprite": "Plane", "speed": 1, "hp": 1, "damage": 1, "bounty": 1 },
+            { "name": "Basic", "sprite": "Plane", "speed": 1, "hp": 1, "damage": 1, "bounty": 1 }
+        ] }"#,
+    )
+    .unwrap_err();
+    assert!(matches!(duplicate, PlaneKindError::Duplicate(ref name) if name == "Basic"));
+
+    let stalled = PlaneRegistry::from_json(
+        r#"{ "planes": [
+            { "name": "Stalled", "sprite": "Plane", "speed": 0, "hp": 1, "damage": 1, "bounty": 1 }
+        ] }"#,
+    )
+    .unwrap_err();
+    assert_eq!(
+        stalled.to_string(),
+        "plane kind \"Stalled\": speed must be a positive number"
+    );
+
+    let sponge = PlaneRegistry::from_json(
+        r#"{ "planes": [
+            { "name": "Sponge", "sprite": "Plane", "speed": 1, "hp": 1, "damage": 1, "bounty": 1,
+              "resistances": { "water": 2.0 } }
+        ] }"#,
+    )
+    .unwrap_err();
+    let unknown = PlaneRegistry::from_json(
+        r#"{ "planes": [
+            { "name": "Kite", "sprite": "Plane", "speed": 1, "hp": 1, "damage": 1, "bounty": 1,
+              "children": [ { "plane": "Ghost", "count": 1 } ] }
+        ] }"#,
+    )
+    .unwrap_err();
+    assert_eq!(
+        unknown.to_string(),
+        "plane kind \"Kite\": unknown child plane \"Ghost\""
+    );
+
+    let cycle = PlaneRegistry::from_json(
+        r#"{ "planes": [
+            { "name": "Egg", "sprite": "Plane", "speed": 1, "hp": 1, "damage": 1, "bounty": 1,
+              "children": [ { "plane": "Hen", "count": 1 } ] },
+            { "name": "Hen", "sprite": "Plane", "speed": 1, "hp": 1, "damage": 1, "bounty": 1,
+              "children": [ { "plane": "Egg", "count": 2 } ] }
+        ] }"#,
+    )
+    .unwrap_err();
+    assert!(matches!(cycle, PlaneKindError::ChildCycle(_)));
+
+    let unseen = PlaneRegistry::from_json(
+        r#"{ "planes": [
+            { "name": "Ghost", "sprite": "Ghost", "speed": 1, "hp": 1, "damage": 1, "bounty": 1 }
+        ] }"#,
+    )
+    .unwrap_err();
+    assert_eq!(
+        unseen.to_string(),
+        "plane kind \"Ghost\": unknown sprite \"Ghost\""
+    );
+
+    let typo = PlaneRegistry::from_json(
+        r#"{ "planes": [
+            { "name": "Ghost", "sprite": "Plane", "speed": 1, "hp": 1, "damage": 1, "bounty": 1,
+              "traits": ["cammo"] }
+        ] }"#,
+    )
+    .unwrap_err();
+    assert!(matches!(typo, PlaneKindError::Parse(_)));
+
+    let unordered = PlaneRegistry::from_json(
+        r#"{ "planes": [
+            { "name": "Titan", "sprite": "Blimp", "speed": 1, "hp": 1, "damage": 1, "bounty": 1,
+              "boss": true, "phases": [ { "hp": 0.25 }, { "hp": 0.5 } ] }
+        ] }"#,
+    )
+    .unwrap_err();
+    assert!(matches!(
+        unordered,
+        PlaneKindError::InvalidPhase { phase: 2, .. }
+    ));
+    let minion = PlaneRegistry::from_json(
+        r#"{ "planes": [
+            { "name": "Minion", "sprite": "Plane", "speed": 1, "hp": 1, "damage": 1, "bounty": 1,
+              "phases": [ { "hp": 0.5 } ] }
+        ] }"#,
+    )
+    .unwrap_err();
+    assert!(matches!(minion, PlaneKindError::NotABoss(ref name) if name == "Minion"));
+    let summoner = PlaneRegistry::from_json(
+        r#"{ "planes": [
+            { "name": "Titan", "sprite": "Blimp", "speed": 1, "hp": 1, "damage": 1, "bounty": 1,
+              "boss": true, "phases": [ { "hp": 0.5,
+                  "escorts": [ { "plane": "Titan", "count": 1 } ] } ] }
+        ] }"#,
+    )
+    .unwrap_err();
+    assert!(matches!(summoner, PlaneKindError::ChildCycle(ref name) if name == "Titan"));
+
+    assert_eq!(
+        sponge.to_string(),
+        "plane kind \"Sponge\": the resistance to water must be a finite number of at most 1"
+    );
+}
@@ -0,0 +1,173 @@
+//! Snapshot tests of what the Simulation draws, recorded without a browser.
+
+use waterguns_vs_paperplanes_wasm::{
+    render::{DrawCommand, RecordingRenderer},
+    simulation::{Input, Simulation},
+    types::{Map, PlaneRegistry, TowerRegistry, WaveSchedule},
+};
+
+const WIDTH: f64 = 1366.0;
+const HEIGHT: f64 = 768.0;
+
+fn idle() -> Input {
+    Input::new(0.0, 0.0, false, false)
+}
+
+fn click(x: f64, y: f64) -> Input {
+    Input::new(x, y, false, true)
+}
+
+fn record(sim: &Simulation) -> Vec<DrawCommand> {
+    let mut renderer = RecordingRenderer::new();
+    sim.draw(&mut renderer).unwrap();
+    renderer.commands().to_vec()
+}
+
+fn texts(commands: &[DrawCommand]) -> Vec<&str> {
+    commands
+        .iter()
+        .filter_map(|command| match command {
+            DrawCommand::Text { text, .. } => Some(text.as_str()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn top_bar_shows_hp_and_cash() {
+    let sim = Simulation::new(WIDTH, HEIGHT);
+    let commands = record(&sim);
+
+    assert_eq!(texts(&commands), ["❤️ : 100", "$  : 10"]);
+
+    let sprites: Vec<_> = commands
+        .iter()
+        .filter_map(|command| match command {
+            DrawCommand::Sprite { name, .. } => Some(name.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        sprites,
+        ["Map", "WaterGunTop", "AcidTowerTop", "SodaMakerTop"]
+    );
+}
+
+#[test]
+fn selected_tower_shows_its_buttons() {
+    let mut sim = Simulation::new(WIDTH, HEIGHT);
+    let button = sim.buttons()[0].rect().clone();
+    let (x, y) = (WIDTH * 0.3, HEIGHT * 0.5);
+
+    sim.step(&click(button.center_x(), button.center_y()));
+    // the click that places a Tower also selects it, offering to undo the placement
+    sim.step(&click(x, y));
+    let commands = record(&sim);
+    let tower = &sim.towers()[0];
+
+    assert!(commands.contains(&DrawCommand::StrokeEllipse {
+        x: tower.center_x(),
+        y: tower.center_y(),
+        radius_x: sim.tower_size() * 0.8,
+        radius_y: sim.tower_size() * 0.8,
+        color: String::from("#00ff00"),
+    }));
+    assert_eq!(
+        texts(&commands),
+        [
+            "Super Soaker",
+            "Pressure Washer",
+            "Undo $10",
+            "First",
+            "❤️ : 100",
+            "$  : 0"
+        ]
+    );
+}
+
+#[test]
+fn healthy_planes_have_full_hp_bars() {
+    let mut sim = Simulation::new(WIDTH, HEIGHT);
+    while sim.planes().is_empty() {
+        sim.step(&idle());
+    }
+    let plane_size = sim.planes()[0].w();
+
+    let full_bars = record(&sim)
+        .into_iter()
+        .filter(|command| match command {
+            DrawCommand::FillRect { rect, color } => {
+                color == "#00ff00" && rect.w() == plane_size.floor()
+            }
+            _ => false,
+        })
+        .count();
+    assert_eq!(full_bars, sim.planes().len());
+}
+
+#[test]
+fn bosses_show_their_hp_in_the_top_bar() {
+    let kinds = PlaneRegistry::from_json(include_str!("../data/planes.json")).unwrap();
+    let towers = TowerRegistry::from_json(include_str!("../data/towers.json")).unwrap();
+    let map = Map::from_json(include_str!("../data/maps/backyard.json")).unwrap();
+    let schedule = WaveSchedule::from_json(
+        r#"{ "waves": [ { "delay": 0, "groups": [
+            { "plane": "Basic", "count": 1, "spacing": 0 },
+            { "plane": "Mothership", "count": 1, "spacing": 0 }
+        ] } ] }"#,
+        |name| kinds.contains(name),
+        |name| map.path(name).is_some(),
+    )
+    .unwrap();
+    let mut sim = Simulation::with_data(WIDTH, HEIGHT, map, kinds, towers, schedule).unwrap();
+    sim.step(&idle());
+
+    let commands = record(&sim);
+    assert_eq!(texts(&commands), ["Mothership", "❤️ : 100", "$  : 10"]);
+    // only the Basic has a bar of its own
+    let bars: Vec<_> = commands
+        .iter()
+        .filter_map(|command| match command {
+            DrawCommand::FillRect { rect, color } if color == "#00ff00" || color == "#ff0000" => {
+                Some(rect.w())
+            }
+            _ => None,
+        })
+        .collect();
+    assert_eq!(bars, [sim.planes()[0].w().floor(), (WIDTH * 0.35).floor()]);
+}
+
+#[test]
+fn selected_build_button_previews_the_tower_at_the_mouse() {
+    let mut sim = Simulation::new(WIDTH, HEIGHT);
+    let button = sim.buttons()[0].rect().clone();
+    let size = sim.tower_size();
+    let range = size * sim.tower_kinds().families()[0].tiers()[0].range();
+    // the ring is centered on the Rect of the previewed Tower
+    let ring = |x: f64, y: f64, color: &str| DrawCommand::StrokeEllipse {
+        x: (x - size / 2.0) + size * 0.5,
+        y: (y - size / 2.0) + size * 0.5,
+        radius_x: range,
+        radius_y: range,
+        color: String::from(color),
+    };
+
+    sim.step(&click(button.center_x(), button.center_y()));
+    let (x, y) = (WIDTH * 0.3, HEIGHT * 0.5);
+    sim.step(&Input::new(x, y, false, false));
+    let commands = record(&sim);
+    assert!(commands.contains(&ring(x, y, "#00ff00")));
+    assert!(commands.contains(&DrawCommand::Alpha { alpha: 0.5 }));
+    assert!(matches!(commands.last(), Some(DrawCommand::Text { .. })));
+
+    // over the path the preview turns red
+    let (x, y) = sim.path().points()[1];
+    sim.step(&Input::new(x, y, false, false));
+    assert!(record(&sim).contains(&ring(x, y, "#ff0000")));
+
+    // nothing is previewed over the top bar
+    sim.step(&Input::new(x, sim.tower_size() * 0.5, false, false));
+    assert!(!record(&sim)
+        .iter()
+        .any(|command| matches!(command, DrawCommand::Alpha { .. })));
+}
@@ -0,0 +1,110 @@
+//! Test suite for the browser-independent Simulation.
+
+use waterguns_vs_paperplanes_wasm::{
+    simulation::{Input, Simulation},
+    types::PlacementError,
+};
+
+const WIDTH: f64 = 1366.0;
+const HEIGHT: f64 = 768.0;
+
+fn idle() -> Input {
+    Input::new(0.0, 0.0, false, false)
+}
+
+fn click(x: f64, y: f64) -> Input {
+    Input::new(x, y, false, true)
+}
+
+#[test]
+fn first_round_spawns_planes() {
+    let mut sim = Simulation::new(WIDTH, HEIGHT);
+    assert!(sim.planes().is_empty());
+
+    for _ in 0..=100 {
+        sim.step(&idle());
+    }
+    assert_eq!(sim.round(), 2);
+    assert_eq!(sim.planes().len(), 1);
+
+    for _ in 0..19 * 89 {
+        sim.step(&idle());
+    }
+    assert_eq!(sim.planes().len(), 20);
+}
+
+#[test]
+fn buying_a_tower_spends_cash() {
+    let mut sim = Simulation::new(WIDTH, HEIGHT);
+    let button = sim.buttons()[0].rect().clone();
+
+    sim.step(&click(button.center_x(), button.center_y()));
+    assert!(sim.buttons()[0].selected());
+
+    sim.step(&click(WIDTH * 0.3, HEIGHT * 0.5));
+    assert_eq!(sim.towers().len(), 1);
+    assert_eq!(sim.cash(), 0);
+}
+
+#[test]
+fn invalid_placements_are_rejected_with_feedback() {
+    let mut sim = Simulation::new(WIDTH, HEIGHT);
+    let size = sim.tower_size();
+    let button = sim.buttons()[0].rect().clone();
+    let (x, y) = sim.path().points()[1];
+
+    for (at, error) in [
+        ((x, y), PlacementError::TooCloseToPath),
+        ((WIDTH * 0.3, size * 1.3), PlacementError::OverlapsUi),
+        (
+            (WIDTH - size * 0.2, HEIGHT * 0.5),
+            PlacementError::OutOfBounds,
+        ),
+    ] {
+        sim.step(&click(button.center_x(), button.center_y()));
+        sim.step(&click(at.0, at.1));
+        assert!(sim.towers().is_empty());
+        assert_eq!(sim.cash(), 10);
+        assert_eq!(sim.feedback(), Some(error));
+    }
+
+    // the feedback fades after a while
+    for _ in 0..120 {
+        sim.step(&idle());
+    }
+    assert_eq!(sim.feedback(), None);
+
+    sim.step(&click(button.center_x(), button.center_y()));
+    sim.step(&click(WIDTH * 0.3, HEIGHT * 0.5));
+    assert_eq!(sim.towers().len(), 1);
+    let rect = sim.towers()[0].rect().clone();
+    assert_eq!(sim.can_place(&rect), Err(PlacementError::OverlapsTower));
+}
+
+#[test]
+fn leaking_planes_damage_the_player() {
+    let mut sim = Simulation::new(WIDTH, HEIGHT);
+    while sim.planes().is_empty() {
+        sim.step(&idle());
+    }
+    for _ in 0..5000 {
+        sim.step(&idle());
+    }
+    assert!(sim.hp().curr_hp() < 100);
+}
+
+#[test]
+fn identical_inputs_give_identical_outcomes() {
+    let run = || {
+        let mut sim = Simulation::new(WIDTH, HEIGHT);
+        let button = sim.buttons()[0].rect().clone();
+        sim.step(&click(button.center_x(), button.center_y()));
+        sim.step(&click(WIDTH * 0.3, HEIGHT * 0.5));
+        for _ in 0..3000 {
+            sim.step(&idle());
+        }
+        let planes: Vec<_> = sim.planes().iter().map(|p| (p.x(), p.y())).collect();
+        (sim.clock().tick(), sim.cash(), sim.hp().curr_hp(), planes)
+    };
+    assert_eq!(run(), run());
+}
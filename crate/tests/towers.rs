@@ -0,0 +1,567 @@
+//! Tests for loading TowerFamilies, upgrading Towers along their branches, and targeting.
+
+use std::rc::Rc;
+
+use waterguns_vs_paperplanes_wasm::{
+    entity::{PaperPlane, Shot, Tower},
+    render::{DrawCommand, RecordingRenderer},
+    simulation::{Input, Simulation},
+    types::{
+        ButtonType, Clock, Difficulty, Map, PlanePath, PlaneRegistry, Rect, TargetMode,
+        TowerKindError, TowerRegistry, TowerStatus, TowerTier, WaveSchedule,
+    },
+};
+
+const FAMILIES: &str = r#"{ "towers": [
+    { "name": "Hose", "base": "WaterGunBase", "tiers": [
+        { "name": "Hose", "top": "WaterGunTop", "blast": "WaterGunBlast",
+          "cost": 15, "range": 2.0, "damage": 3, "interval": 10,
+          "upgrades": ["Fire Hose", "Garden Sprinkler"] },
+        { "name": "Fire Hose", "top": "SuperSoakerTop", "blast": "SuperSoakerBlast",
+          "cost": 25, "range": 2.5, "damage": 6, "interval": 8,
+          "upgrades": ["Fire Truck"] },
+        { "name": "Fire Truck", "top": "ExtremeSoakerTop", "blast": "ExtremeSoakerBlast",
+          "cost": 40, "range": 3.0, "damage": 9, "interval": 6 },
+        { "name": "Garden Sprinkler", "top": "RootBeerTop", "blast": "RootBeerBlast",
+          "cost": 80, "range": 4.0, "damage": 20, "interval": 4 }
+    ] }
+] }"#;
+
+#[test]
+fn upgrades_follow_the_chosen_branch() {
+    let towers = TowerRegistry::from_json(FAMILIES).unwrap();
+    let mut tower = Tower::build(&towers.families()[0], Rect::new(0.0, 0.0, 10.0, 10.0), 1.0);
+    assert_eq!(tower.tier().name(), "Hose");
+    assert_eq!(tower.range(), 20.0);
+    assert_eq!(
+        tower.upgrades().map(TowerTier::name).collect::<Vec<_>>(),
+        ["Fire Hose", "Garden Sprinkler"]
+    );
+    assert_eq!(tower.upgrade_buttons().len(), 2);
+
+    // the Garden Sprinkler costs more than the player has
+    let mut cash = 70;
+    tower.upgrade(3, &mut cash);
+    assert_eq!(tower.tier().name(), "Hose");
+    assert_eq!(cash, 70);
+
+    // the Fire Truck is not a branch of the Hose
+    tower.upgrade(2, &mut cash);
+    assert_eq!(tower.tier().name(), "Hose");
+
+    tower.upgrade(1, &mut cash);
+    tower.upgrade(2, &mut cash);
+    assert_eq!(tower.tier().name(), "Fire Truck");
+    assert_eq!(tower.range(), 30.0);
+    assert_eq!(cash, 5);
+
+    // choosing the Fire Hose closed off the Garden Sprinkler
+    cash = 1000;
+    tower.upgrade(3, &mut cash);
+    assert_eq!(tower.tier().name(), "Fire Truck");
+    assert_eq!(cash, 1000);
+    assert!(tower.upgrade_buttons().is_empty());
+}
+
+#[test]
+fn towers_target_planes_by_their_mode() {
+    let towers = TowerRegistry::from_json(FAMILIES).unwrap();
+    let planes = PlaneRegistry::from_json(include_str!("../data/planes.json")).unwrap();
+    let basic = planes.get("Basic").unwrap();
+    let blimp = planes.get("Blimp").unwrap();
+
+    // the range reaches 20 pixels from the center of the Tower at (5, 5)
+    let mut tower = Tower::build(&towers.families()[0], Rect::new(0.0, 0.0, 10.0, 10.0), 1.0);
+    // fly the Planes along a straight path through the Tower to different distances
+    let straight = Rc::new(PlanePath::new(vec![(-20.0, 5.0), (200.0, 5.0)]));
+    let mut planes = Vec::new();
+    for (kind, ticks) in [(basic, 100), (basic, 25), (blimp, 10), (basic, 20)] {
+        let mut plane = PaperPlane::spawn(0, kind, &straight, 2.0);
+        for _ in 0..ticks {
+            plane.fly();
+        }
+        planes.push(plane);
+    }
+    planes[3].hp_mut().take_damage(1);
+
+    let mut targets = Vec::new();
+    for _ in 0..5 {
+        targets.push((tower.target_mode(), tower.target(&planes)));
+        tower.set_target_mode(tower.target_mode().next());
+    }
+    assert_eq!(
+        targets,
+        [
+            (TargetMode::First, Some(1)),
+            (TargetMode::Last, Some(2)),
+            (TargetMode::Strongest, Some(2)),
+            (TargetMode::Weakest, Some(3)),
+            (TargetMode::Closest, Some(3)),
+        ]
+    );
+    assert_eq!(tower.target_mode(), TargetMode::First);
+
+    // on paths of different lengths the Plane closest to its exit is first, even if it has
+    // travelled less
+    let long = Rc::new(PlanePath::new(vec![(-195.0, 5.0), (205.0, 5.0)]));
+    let short = Rc::new(PlanePath::new(vec![(5.0, -45.0), (5.0, 55.0)]));
+    let crossing = [
+        PaperPlane::spawn_at(0, basic, &long, 2.0, 200.0),
+        PaperPlane::spawn_at(1, basic, &short, 2.0, 50.0),
+    ];
+    assert_eq!(tower.target(&crossing), Some(1));
+    tower.set_target_mode(TargetMode::Last);
+    assert_eq!(tower.target(&crossing), Some(0));
+
+    // only the chosen Plane is hit, and only once per interval, through the Blimp's armor
+    let mut clock = Clock::new();
+    tower.set_target_mode(TargetMode::Strongest);
+    tower.attack(&mut planes, &clock);
+    clock.advance();
+    tower.attack(&mut planes, &clock);
+    let damage = |plane: &PaperPlane| plane.hp().max_hp() - plane.hp().curr_hp();
+    assert_eq!(planes.iter().map(damage).collect::<Vec<_>>(), [0, 0, 1, 1]);
+}
+
+#[test]
+fn fast_planes_outrun_slow_projectiles() {
+    let towers = TowerRegistry::from_json(
+        r#"{ "towers": [
+            { "name": "Squirt Gun", "base": "WaterGunBase", "tiers": [
+                { "name": "Squirt Gun", "top": "WaterGunTop", "blast": "WaterGunBlast",
+                  "cost": 10, "range": 10.0, "damage": 5, "interval": 10,
+                  "projectile": { "sprite": "WaterGunBlast", "speed": 2.0, "radius": 0.2 } }
+            ] },
+            { "name": "Seeker", "base": "WaterGunBase", "tiers": [
+                { "name": "Seeker", "top": "WaterGunTop", "blast": "WaterGunBlast",
+                  "cost": 10, "range": 10.0, "damage": 5, "interval": 10,
+                  "projectile": { "sprite": "WaterGunBlast", "speed": 6.0, "homing": true,
+                                  "radius": 0.2 } }
+            ] }
+        ] }"#,
+    )
+    .unwrap();
+    let planes = PlaneRegistry::from_json(include_str!("../data/planes.json")).unwrap();
+    let path = Rc::new(PlanePath::new(vec![(-60.0, 45.0), (200.0, 45.0)]));
+
+    let mut damage = Vec::new();
+    for family in towers.families() {
+        let mut tower = Tower::build(family, Rect::new(0.0, 0.0, 10.0, 10.0), 1.0);
+        let mut planes = vec![PaperPlane::spawn(
+            7,
+            planes.get("BulletRedux").unwrap(),
+            &path,
+            10.0,
+        )];
+
+        // the shot travels instead of hitting instantly
+        let clock = Clock::new();
+        let mut projectile = match tower.attack(&mut planes, &clock) {
+            Some(Shot::Projectile(projectile)) => projectile,
+            _ => panic!("expected a Projectile"),
+        };
+        assert_eq!(projectile.target(), 7);
+        assert_eq!(planes[0].hp().curr_hp(), 30);
+
+        while !projectile.spent() {
+            planes[0].fly();
+            assert!(projectile.fly(&mut planes, &clock).is_none());
+        }
+        damage.push(30 - planes[0].hp().curr_hp());
+    }
+    assert_eq!(damage, [0, 5]);
+}
+
+#[test]
+fn splashes_damage_nearby_planes_with_falloff() {
+    let towers = TowerRegistry::from_json(
+        r#"{ "towers": [ { "name": "Fizz", "base": "WaterGunBase", "tiers": [
+            { "name": "Fizz", "top": "SodaMakerTop", "blast": "SodaMakerBlast",
+              "cost": 10, "range": 10.0, "damage": 10, "interval": 10,
+              "splash": { "radius": 2.0, "falloff": 0.5 } }
+        ] } ] }"#,
+    )
+    .unwrap();
+    let planes = PlaneRegistry::from_json(
+        r#"{ "planes": [
+            { "name": "Drifter", "sprite": "Plane", "speed": 1.0, "hp": 50, "damage": 1, "bounty": 1 }
+        ] }"#,
+    )
+    .unwrap();
+    let path = Rc::new(PlanePath::new(vec![(-100.0, 5.0), (300.0, 5.0)]));
+
+    // the Planes trail the first by 8 and 30 pixels
+    let mut planes: Vec<_> = IntoIterator::into_iter([50, 42, 20])
+        .enumerate()
+        .map(|(id, ticks)| {
+            let mut plane =
+                PaperPlane::spawn(id as u64, planes.get("Drifter").unwrap(), &path, 2.0);
+            for _ in 0..ticks {
+                plane.fly();
+            }
+            plane
+        })
+        .collect();
+
+    let mut tower = Tower::build(&towers.families()[0], Rect::new(0.0, 0.0, 10.0, 10.0), 1.0);
+    let burst = match tower.attack(&mut planes, &Clock::new()) {
+        Some(Shot::Burst(burst)) => burst,
+        _ => panic!("expected a Burst"),
+    };
+    assert_eq!((burst.x(), burst.y(), burst.radius()), (-50.0, 5.0, 20.0));
+
+    let damage = |plane: &PaperPlane| plane.hp().max_hp() - plane.hp().curr_hp();
+    assert_eq!(planes.iter().map(damage).collect::<Vec<_>>(), [10, 8, 0]);
+}
+
+#[test]
+fn camo_planes_hide_from_towers_without_detection() {
+    let towers = TowerRegistry::from_json(
+        r#"{ "towers": [ { "name": "Lookout", "base": "WaterGunBase", "tiers": [
+            { "name": "Lookout", "top": "WaterGunTop", "blast": "WaterGunBlast",
+              "cost": 10, "range": 10.0, "damage": 1, "interval": 10, "upgrades": ["Radar"] },
+            { "name": "Radar", "top": "SuperSoakerTop", "blast": "SuperSoakerBlast",
+              "cost": 10, "range": 10.0, "damage": 1, "interval": 10, "detection": true }
+        ] } ] }"#,
+    )
+    .unwrap();
+    let kinds = PlaneRegistry::from_json(
+        r#"{ "planes": [
+            { "name": "Ghost", "sprite": "Plane", "speed": 1.0, "hp": 10, "damage": 1, "bounty": 1,
+              "traits": ["camo"] },
+            { "name": "Kite", "sprite": "Plane", "speed": 1.0, "hp": 10, "damage": 1, "bounty": 1 }
+        ] }"#,
+    )
+    .unwrap();
+    let path = Rc::new(PlanePath::new(vec![(-50.0, 5.0), (200.0, 5.0)]));
+    let mut planes = Vec::new();
+    for (id, (name, ticks)) in IntoIterator::into_iter([("Ghost", 30), ("Kite", 10)]).enumerate() {
+        let mut plane = PaperPlane::spawn(id as u64, kinds.get(name).unwrap(), &path, 2.0);
+        for _ in 0..ticks {
+            plane.fly();
+        }
+        planes.push(plane);
+    }
+
+    // the Ghost is further along but hidden
+    let mut tower = Tower::build(&towers.families()[0], Rect::new(0.0, 0.0, 10.0, 10.0), 1.0);
+    assert!(!tower.detects());
+    assert_eq!(tower.target(&planes), Some(1));
+
+    let mut renderer = RecordingRenderer::new();
+    planes[0].draw(&mut renderer, &Clock::new()).unwrap();
+    assert_eq!(renderer.commands()[0], DrawCommand::Alpha { alpha: 0.4 });
+
+    tower.upgrade(1, &mut 10);
+    assert!(tower.detects());
+    assert_eq!(tower.target(&planes), Some(0));
+}
+
+#[test]
+fn shots_pass_through_camo_planes_their_tower_cannot_see() {
+    let towers = TowerRegistry::from_json(
+        r#"{ "towers": [
+            { "name": "Squirt Gun", "base": "WaterGunBase", "tiers": [
+                { "name": "Squirt Gun", "top": "WaterGunTop", "blast": "WaterGunBlast",
+                  "cost": 10, "range": 10.0, "damage": 10, "interval": 10,
+                  "projectile": { "sprite": "WaterGunBlast", "speed": 6.0, "radius": 0.2 },
+                  "splash": { "radius": 2.0, "falloff": 0.5 } }
+            ] },
+            { "name": "Periscope", "base": "WaterGunBase", "tiers": [
+                { "name": "Periscope", "top": "WaterGunTop", "blast": "WaterGunBlast",
+                  "cost": 10, "range": 10.0, "damage": 10, "interval": 10, "detection": true,
+                  "projectile": { "sprite": "WaterGunBlast", "speed": 6.0, "radius": 0.2 },
+                  "splash": { "radius": 2.0, "falloff": 0.5 } }
+            ] }
+        ] }"#,
+    )
+    .unwrap();
+    let kinds = PlaneRegistry::from_json(
+        r#"{ "planes": [
+            { "name": "Ghost", "sprite": "Plane", "speed": 1.0, "hp": 50, "damage": 1, "bounty": 1,
+              "traits": ["camo"] },
+            { "name": "Kite", "sprite": "Plane", "speed": 1.0, "hp": 50, "damage": 1, "bounty": 1 }
+        ] }"#,
+    )
+    .unwrap();
+    let path = Rc::new(PlanePath::new(vec![(-100.0, 5.0), (300.0, 5.0)]));
+
+    let mut damage = Vec::new();
+    for family in towers.families() {
+        // the hidden Ghost flies 8 pixels behind the Kite, in the way of the shot
+        let mut planes: Vec<_> = IntoIterator::into_iter([("Kite", 150), ("Ghost", 142)])
+            .enumerate()
+            .map(|(id, (name, ticks))| {
+                let mut plane = PaperPlane::spawn(id as u64, kinds.get(name).unwrap(), &path, 2.0);
+                for _ in 0..ticks {
+                    plane.fly();
+                }
+                plane
+            })
+            .collect();
+
+        let mut tower = Tower::build(family, Rect::new(0.0, 0.0, 10.0, 10.0), 1.0);
+        let clock = Clock::new();
+        let mut projectile = match tower.attack(&mut planes, &clock) {
+            Some(Shot::Projectile(projectile)) => projectile,
+            _ => panic!("expected a Projectile"),
+        };
+        assert_eq!(projectile.target(), 0);
+        while !projectile.spent() {
+            projectile.fly(&mut planes, &clock);
+        }
+        let hurt = |plane: &PaperPlane| plane.hp().max_hp() - plane.hp().curr_hp();
+        damage.push(planes.iter().map(hurt).collect::<Vec<_>>());
+    }
+    // neither the shot nor its splash touch the Ghost unless the Tower can see it
+    assert_eq!(damage, [[10, 0], [8, 10]]);
+}
+
+#[test]
+fn towers_with_detection_reveal_camo_planes_in_range() {
+    let map = Map::from_json(
+        r#"{ "name": "Lane", "background": "Map",
+             "paths": [ { "name": "main", "spawn": [0, 0.5], "exit": [1, 0.5] } ] }"#,
+    )
+    .unwrap();
+    let kinds = PlaneRegistry::from_json(
+        r#"{ "planes": [
+            { "name": "Ghost", "sprite": "Plane", "speed": 5.0, "hp": 10, "damage": 1, "bounty": 1,
+              "traits": ["camo"] }
+        ] }"#,
+    )
+    .unwrap();
+    let towers = TowerRegistry::from_json(
+        r#"{ "towers": [ { "name": "Radar", "base": "WaterGunBase", "tiers": [
+            { "name": "Radar", "top": "SuperSoakerTop", "blast": "SuperSoakerBlast",
+              "cost": 10, "range": 2.0, "damage": 0, "interval": 10, "detection": true }
+        ] } ] }"#,
+    )
+    .unwrap();
+    let schedule = WaveSchedule::from_json(
+        r#"{ "waves": [ { "delay": 0, "groups": [
+            { "plane": "Ghost", "count": 1, "spacing": 0 }
+        ] } ] }"#,
+        |name| kinds.contains(name),
+        |name| name == "main",
+    )
+    .unwrap();
+    let mut sim = Simulation::with_data(1000.0, 500.0, map, kinds, towers, schedule).unwrap();
+    let button = sim.buttons()[0].rect().clone();
+    sim.step(&Input::new(
+        button.center_x(),
+        button.center_y(),
+        false,
+        true,
+    ));
+    sim.step(&Input::new(500.0, 300.0, false, true));
+    assert_eq!(sim.towers().len(), 1);
+
+    // the Ghost is only revealed while it flies within range of the Radar
+    let mut revealed = Vec::new();
+    while sim.planes()[0].center_x() < 700.0 {
+        sim.step(&Input::new(0.0, 0.0, false, false));
+        let ghost = &sim.planes()[0];
+        assert_eq!(ghost.targetable(), ghost.revealed());
+        revealed.push((ghost.center_x(), ghost.revealed()));
+    }
+    let range = sim.tower_size() * 2.0;
+    for (x, revealed) in revealed {
+        let distance = (x - 500.0).hypot(250.0 - 300.0);
+        if (distance - range).abs() > 5.0 {
+            assert_eq!(revealed, distance <= range, "at x = {}", x);
+        }
+    }
+}
+
+#[test]
+fn selling_refunds_a_share_of_the_investment() {
+    let towers = TowerRegistry::from_json(FAMILIES).unwrap();
+    let rate = Difficulty::Hard.sell_rate();
+    let mut tower = Tower::build(&towers.families()[0], Rect::new(0.0, 0.0, 10.0, 10.0), rate);
+    assert_eq!(tower.invested(), 15);
+    assert_eq!(tower.sell_price(), 7);
+
+    let mut cash = 100;
+    tower.upgrade(1, &mut cash);
+    tower.upgrade(2, &mut cash);
+    assert_eq!(tower.invested(), 80);
+    assert_eq!(tower.sell_price(), 40);
+
+    // sell the Tower from its selection panel on a game made easier after it was built
+    let mut sim = Simulation::new(1366.0, 768.0);
+    let button = sim.buttons()[0].rect().clone();
+    let click = |x, y| Input::new(x, y, false, true);
+    sim.step(&click(button.center_x(), button.center_y()));
+    sim.step(&click(1366.0 * 0.3, 768.0 * 0.5));
+    assert_eq!(sim.cash(), 0);
+    assert_eq!(sim.towers()[0].sell_price(), 7);
+    sim.set_difficulty("easy".parse().unwrap());
+    assert_eq!(sim.towers()[0].sell_price(), 9);
+
+    // wait out the undo window, then sell and confirm
+    for _ in 0..180 {
+        sim.step(&Input::new(0.0, 0.0, false, false));
+    }
+    let tower = &sim.towers()[0];
+    assert_eq!(tower.status(), TowerStatus::Selected);
+    let sell = (tower.center_x(), tower.rect().y() + tower.rect().h() * 1.55);
+    sim.step(&click(sell.0, sell.1));
+    assert_eq!(sim.towers()[0].status(), TowerStatus::ConfirmSell);
+    assert_eq!(sim.cash(), 0);
+    sim.step(&click(sell.0, sell.1));
+    assert!(sim.towers().is_empty());
+    assert_eq!(sim.cash(), 9);
+}
+
+#[test]
+fn sells_are_confirmed_and_fresh_placements_undone() {
+    let mut sim = Simulation::new(1366.0, 768.0);
+    let button = sim.buttons()[0].rect().clone();
+    let click = |x, y| Input::new(x, y, false, true);
+    let place = |sim: &mut Simulation| {
+        sim.step(&click(button.center_x(), button.center_y()));
+        sim.step(&click(1366.0 * 0.3, 768.0 * 0.5));
+        let tower = &sim.towers()[0];
+        (tower.center_x(), tower.rect().y() + tower.rect().h() * 1.55)
+    };
+
+    // a Tower removed right after being placed is refunded in full
+    let undo = place(&mut sim);
+    assert!(matches!(
+        sim.towers()[0].status(),
+        TowerStatus::Placed { selected: true, .. }
+    ));
+    sim.step(&click(undo.0, undo.1));
+    assert!(sim.towers().is_empty());
+    assert_eq!(sim.cash(), 10);
+
+    // the undo window outlasts deselecting and reselecting the Tower
+    let undo = place(&mut sim);
+    let center = sim.towers()[0].rect().clone();
+    sim.step(&click(center.center_x(), center.center_y()));
+    assert!(matches!(
+        sim.towers()[0].status(),
+        TowerStatus::Placed {
+            selected: false,
+            ..
+        }
+    ));
+    for _ in 0..100 {
+        sim.step(&Input::new(0.0, 0.0, false, false));
+    }
+    sim.step(&click(center.center_x(), center.center_y()));
+    assert!(matches!(
+        sim.towers()[0].status(),
+        TowerStatus::Placed { selected: true, .. }
+    ));
+    sim.step(&click(undo.0, undo.1));
+    assert!(sim.towers().is_empty());
+    assert_eq!(sim.cash(), 10);
+
+    // clicking anywhere else cancels a pending sell
+    let sell = place(&mut sim);
+    for _ in 0..180 {
+        sim.step(&Input::new(0.0, 0.0, false, false));
+    }
+    sim.step(&click(sell.0, sell.1));
+    assert_eq!(sim.towers()[0].status(), TowerStatus::ConfirmSell);
+    sim.step(&click(1366.0 * 0.6, 768.0 * 0.5));
+    assert_eq!(sim.towers()[0].status(), TowerStatus::Normal);
+    assert_eq!(sim.cash(), 0);
+}
+
+#[test]
+fn every_family_gets_a_build_button() {
+    let default = TowerRegistry::from_json(include_str!("../data/towers.json")).unwrap();
+    let extra = TowerRegistry::from_json(FAMILIES).unwrap();
+    let planes = PlaneRegistry::from_json(include_str!("../data/planes.json")).unwrap();
+    let waves = || {
+        WaveSchedule::from_json(
+            include_str!("../data/waves.json"),
+            |name| planes.contains(name),
+            |name| name == "main",
+        )
+        .unwrap()
+    };
+
+    let map = || Map::from_json(include_str!("../data/maps/backyard.json")).unwrap();
+
+    let sim =
+        Simulation::with_data(1366.0, 768.0, map(), planes.clone(), default, waves()).unwrap();
+    assert_eq!(sim.buttons().len(), 3);
+
+    let sim = Simulation::with_data(1366.0, 768.0, map(), planes.clone(), extra, waves()).unwrap();
+    assert_eq!(sim.buttons().len(), 1);
+    assert_eq!(sim.buttons()[0].button_type(), ButtonType::Build(0));
+}
+
+#[test]
+fn invalid_families_are_rejected() {
+    let err = TowerRegistry::from_json(
+        r#"{ "towers": [ { "name": "Empty", "base": "WaterGunBase", "tiers": [] } ] }"#,
+    )
+    .unwrap_err();
+    assert!(matches!(err, TowerKindError::NoTiers(ref name) if name == "Empty"));
+
+    let err = TowerRegistry::from_json(
+        r#"{ "towers": [ { "name": "Jammed", "base": "WaterGunBase", "tiers": [
+            { "name": "Jammed", "top": "WaterGunTop", "blast": "WaterGunBlast",
+              "cost": 10, "range": 2.0, "damage": 1, "interval": 0 }
+        ] } ] }"#,
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "tower family \"Jammed\", tier \"Jammed\": interval must be a positive number"
+    );
+
+    let err = TowerRegistry::from_json(
+        r#"{ "towers": [ { "name": "Loop", "base": "WaterGunBase", "tiers": [
+            { "name": "Loop", "top": "WaterGunTop", "blast": "WaterGunBlast",
+              "cost": 10, "range": 2.0, "damage": 1, "interval": 5, "upgrades": ["Loop"] }
+        ] } ] }"#,
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "tower family \"Loop\", tier \"Loop\": \"Loop\" is not a later tier of the family"
+    );
+
+    let err = TowerRegistry::from_json(
+        r#"{ "towers": [ { "name": "Laser", "base": "WaterGunBase", "tiers": [
+            { "name": "Laser", "top": "WaterGunTop", "blast": "WaterGunBlast",
+              "cost": 10, "range": 2.0, "damage": 1, "interval": 5,
+              "projectile": { "sprite": "LaserBeam", "speed": 10.0, "radius": 0.2 } }
+        ] } ] }"#,
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "tower family \"Laser\": unknown sprite \"LaserBeam\""
+    );
+
+    let err = TowerRegistry::from_json(
+        r##"{ "towers": [ { "name": "Mister", "base": "WaterGunBase", "tiers": [
+            { "name": "Mister", "top": "WaterGunTop", "blast": "WaterGunBlast",
+              "cost": 10, "range": 2.0, "damage": 1, "interval": 5, "effects": [
+                { "name": "soaked", "tint": "#3399ff", "duration": 10, "immune": ["waterprof"] }
+            ] }
+        ] } ] }"##,
+    )
+    .unwrap_err();
+    assert!(matches!(err, TowerKindError::Parse(_)));
+
+    let err = TowerRegistry::from_json(
+        r#"{ "towers": [ { "name": "Geyser", "base": "WaterGunBase", "tiers": [
+            { "name": "Geyser", "top": "WaterGunTop", "blast": "WaterGunBlast",
+              "cost": 10, "range": 2.0, "damage": 1, "interval": 5,
+              "splash": { "radius": 1.0, "falloff": 1.5 } }
+        ] } ] }"#,
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "tower family \"Geyser\", tier \"Geyser\": splash falloff must be between 0 and 1"
+    );
+}
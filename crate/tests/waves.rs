@@ -0,0 +1,173 @@
+//! Tests for loading WaveSchedules and sending their Waves.
+
+use waterguns_vs_paperplanes_wasm::{
+    simulation::{Input, Simulation},
+    types::{Map, PlaneRegistry, TowerRegistry, WaveError, WaveSchedule},
+};
+
+fn idle() -> Input {
+    Input::new(0.0, 0.0, false, false)
+}
+
+fn kinds() -> PlaneRegistry {
+    PlaneRegistry::from_json(include_str!("../data/planes.json")).unwrap()
+}
+
+fn schedule(json: &str) -> Result<WaveSchedule, WaveError> {
+    WaveSchedule::from_json(json, |name| kinds().contains(name), |name| name == "main")
+}
+
+fn simulation(json: &str) -> Simulation {
+    let towers = TowerRegistry::from_json(include_str!("../data/towers.json")).unwrap();
+    let map = Map::from_json(include_str!("../data/maps/backyard.json")).unwrap();
+    Simulation::with_data(1366.0, 768.0, map, kinds(), towers, schedule(json).unwrap()).unwrap()
+}
+
+#[test]
+fn groups_spawn_after_their_delay_and_spacing() {
+    let mut sim = simulation(
+        r#"{ "waves": [ { "delay": 10, "groups": [
+            { "plane": "Basic", "count": 3, "spacing": 5 },
+            { "plane": "Blimp", "count": 1, "spacing": 0, "delay": 20 }
+        ] } ] }"#,
+    );
+
+    let mut counts = Vec::new();
+    for _ in 0..40 {
+        sim.step(&idle());
+        counts.push(sim.planes().len());
+    }
+    assert_eq!(counts[9], 0);
+    assert_eq!(counts[10], 1);
+    assert_eq!(counts[15], 2);
+    assert_eq!(counts[20], 3);
+    assert_eq!(counts[30], 4);
+    assert_eq!(sim.round(), 2);
+}
+
+#[test]
+fn the_boss_is_sent_after_the_last_wave() {
+    let mut sim = simulation(
+        r#"{ "waves": [ { "delay": 0, "groups": [
+            { "plane": "Basic", "count": 1, "spacing": 0 }
+        ] } ], "boss": { "plane": "Mothership", "delay": 20 } }"#,
+    );
+    assert_eq!(sim.schedule().rounds(), 2);
+
+    for _ in 0..20 {
+        sim.step(&idle());
+    }
+    assert_eq!(sim.round(), 2);
+    assert!(sim.boss().is_none());
+
+    sim.step(&idle());
+    assert_eq!(sim.round(), 3);
+    assert_eq!(sim.boss().unwrap().kind().name(), "Mothership");
+    assert!(!sim.is_victorious());
+}
+
+#[test]
+fn modifiers_scale_planes() {
+    let mut sim = simulation(
+        r#"{ "waves": [ { "delay": 0, "groups": [
+            { "plane": "Basic", "count": 1, "spacing": 0 }
+        ], "modifiers": { "hp": 2.5, "bounty": 3.0 } } ] }"#,
+    );
+    sim.step(&idle());
+
+    let plane = &sim.planes()[0];
+    assert_eq!(plane.hp().max_hp(), 25);
+    assert_eq!(plane.bounty(), 3);
+
+    // a Plane is never scaled down to no HP at all
+    let mut sim = simulation(
+        r#"{ "waves": [ { "delay": 0, "groups": [
+            { "plane": "Basic", "count": 1, "spacing": 0 }
+        ], "modifiers": { "hp": 0.01 } } ] }"#,
+    );
+    sim.step(&idle());
+
+    let plane = &sim.planes()[0];
+    assert_eq!(plane.hp().max_hp(), 1);
+    assert!(!plane.hp().is_dead());
+}
+
+#[test]
+fn unknown_planes_are_reported() {
+    let err = schedule(
+        r#"{ "waves": [
+            { "delay": 0, "groups": [ { "plane": "Basic", "count": 1, "spacing": 0 } ] },
+            { "delay": 0, "groups": [ { "plane": "Bulet", "count": 1, "spacing": 0 } ] }
+        ] }"#,
+    )
+    .unwrap_err();
+
+    assert!(matches!(
+        err,
+        WaveError::UnknownPlane {
+            wave: 2,
+            group: 1,
+            ..
+        }
+    ));
+    assert_eq!(
+        err.to_string(),
+        "wave 2, group 1: unknown plane kind \"Bulet\""
+    );
+
+    // a schedule checked against other kinds is checked again when the game starts
+    let schedule = WaveSchedule::from_json(
+        r#"{ "waves": [
+            { "delay": 0, "groups": [ { "plane": "Bulet", "count": 1, "spacing": 0 } ] }
+        ] }"#,
+        |_| true,
+        |name| name == "main",
+    )
+    .unwrap();
+    let towers = TowerRegistry::from_json(include_str!("../data/towers.json")).unwrap();
+    let map = Map::from_json(include_str!("../data/maps/backyard.json")).unwrap();
+    let err = Simulation::with_data(1366.0, 768.0, map, kinds(), towers, schedule)
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.to_string(),
+        "wave 1, group 1: unknown plane kind \"Bulet\""
+    );
+}
+
+#[test]
+fn invalid_documents_are_rejected() {
+    let parse = |json| schedule(json).unwrap_err();
+
+    assert!(matches!(parse("{ \"waves\": [] }"), WaveError::NoWaves));
+    assert!(matches!(parse("{ \"waves\": 3 }"), WaveError::Parse(_)));
+    assert!(matches!(
+        parse(r#"{ "waves": [ { "delay": 0, "groups": [] } ] }"#),
+        WaveError::NoGroups { wave: 1 }
+    ));
+    assert!(matches!(
+        parse(
+            r#"{ "waves": [ { "delay": 0, "groups": [
+                { "plane": "Basic", "count": 0, "spacing": 0 }
+            ] } ] }"#
+        ),
+        WaveError::EmptyGroup { wave: 1, group: 1 }
+    ));
+    assert!(matches!(
+        parse(
+            r#"{ "waves": [ { "delay": 0, "groups": [
+                { "plane": "Basic", "count": 1, "spacing": 0 }
+            ], "modifiers": { "speed": 0 } } ] }"#
+        ),
+        WaveError::InvalidModifier {
+            wave: 1,
+            modifier: "speed"
+        }
+    ));
+    let boss = parse(
+        r#"{ "waves": [ { "delay": 0, "groups": [
+            { "plane": "Basic", "count": 1, "spacing": 0 }
+        ] } ], "boss": { "plane": "Mothership", "delay": 0, "path": "side" } }"#,
+    );
+    assert_eq!(boss.to_string(), "the boss: unknown path \"side\"");
+}
@@ -15,6 +15,9 @@ import { Game } from "waterguns-vs-paperplanes";
         if (game.isDefeated()) {
             alert("YOU WERE DEFEATED");
             document.location.href = "/";
+        } else if (game.isVictorious()) {
+            alert("YOU WON");
+            document.location.href = "/";
         }
         mouseUp = false;
 
@@ -35,6 +38,13 @@ import { Game } from "waterguns-vs-paperplanes";
         mouseDown = false;
     });
 
+    // toggle the placement grid overlay
+    document.addEventListener('keydown', (e: KeyboardEvent) => {
+        if (e.key === 'g') {
+            game.toggleGrid();
+        }
+    });
+
     // prevent the context menu from popping up on a right click
     document.addEventListener('contextmenu', (e: MouseEvent) => {
         e.preventDefault();